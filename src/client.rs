@@ -2,14 +2,17 @@ use reqwest;
 use serenity::model::gateway::GatewayIntents;
 use songbird::serenity::SerenityInit;
 
-use std::{collections::HashMap, env, error::Error};
+use std::{collections::HashMap, env, error::Error, sync::Arc};
 
 use crate::{
+    commands::quiz::QUIZ_CHAT_ANSWERS,
     guild::{
-        cache::GuildCacheMap, http_client::HttpClientInstance, metadata_store::MetadataStore,
-        settings::GuildSettingsMap, stored_queue::GuildStoredQueueMap,
+        cache::GuildCacheMap, favorites::UserFavoritesMap, http_client::HttpClientInstance,
+        metadata_store::MetadataStore, schedule::GuildSchedulesMap, settings::GuildSettingsMap,
+        stored_queue::GuildStoredQueueMap,
     },
     handlers::SerenityHandler,
+    sources::SourceRegistry,
 };
 
 pub struct Client {
@@ -27,7 +30,12 @@ impl Client {
             .expect("Fatality! DISCORD_APP_ID not set!")
             .parse()?;
 
-        let gateway_intents = GatewayIntents::non_privileged();
+        let mut gateway_intents = GatewayIntents::non_privileged();
+
+        // only asked for when enabled, since Discord refuses bots that weren't granted it
+        if *QUIZ_CHAT_ANSWERS {
+            gateway_intents |= GatewayIntents::MESSAGE_CONTENT;
+        }
 
         let client = serenity::Client::builder(token, gateway_intents)
             .event_handler(SerenityHandler)
@@ -37,10 +45,13 @@ impl Client {
 
         let mut data = client.data.write().await;
         data.insert::<GuildCacheMap>(HashMap::default());
+        data.insert::<GuildSchedulesMap>(HashMap::default());
         data.insert::<GuildSettingsMap>(HashMap::default());
         data.insert::<GuildStoredQueueMap>(HashMap::default());
         data.insert::<HttpClientInstance>(reqwest::Client::new());
         data.insert::<MetadataStore>(MetadataStore::new());
+        data.insert::<SourceRegistry>(Arc::new(SourceRegistry::new()));
+        data.insert::<UserFavoritesMap>(HashMap::default());
 
         drop(data);
 
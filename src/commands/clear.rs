@@ -1,6 +1,7 @@
 use crate::{
+    commands::undo::save_undo_snapshot,
     errors::{ParrotError, verify},
-    handlers::track_end::update_queue_messages,
+    handlers::{track_end::update_queue_messages, track_error::forget_restarted_tracks},
     messaging::message::ParrotMessage,
     utils::create_response,
 };
@@ -15,7 +16,9 @@ pub async fn clear(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
     let queue = handler.queue().current_queue();
 
     verify(queue.len() > 1, ParrotError::QueueEmpty)?;
+    save_undo_snapshot(&ctx.data, guild_id, "clear", &queue, 1..queue.len(), false).await;
 
+    let cleared = queue[1..].to_vec();
     handler.queue().modify_queue(|v| {
         v.drain(1..);
     });
@@ -24,6 +27,8 @@ pub async fn clear(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
     let queue = handler.queue().current_queue();
     drop(handler);
 
+    forget_restarted_tracks(&ctx.data, guild_id, &cleared).await;
+
     create_response(&ctx.http, interaction, ParrotMessage::Clear).await?;
     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
     Ok(())
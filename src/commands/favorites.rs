@@ -0,0 +1,316 @@
+use crate::{
+    commands::{
+        play::{Mode, QueryType, normal_query_type_resolver},
+        summon::summon,
+    },
+    connection::{check_command_connection, check_voice_connections},
+    errors::{ParrotError, verify},
+    guild::{
+        favorites::{Favorite, UserFavorites, UserFavoritesMap},
+        metadata_store::MetadataStore,
+        settings::{GuildSettings, GuildSettingsMap},
+        stored_queue::{GuildStoredQueue, GuildStoredQueueMap},
+    },
+    handlers::track_end::update_queue_messages,
+    messaging::{
+        message::ParrotMessage,
+        messages::{
+            FAIL_FAVORITES_EMPTY, FAIL_LIKE_UNAVAILABLE, FAIL_NO_FAVORITE_ON_INDEX,
+            FAVORITES_TITLE, QUEUE_PAGE, QUEUE_PAGE_OF,
+        },
+    },
+    permissions::check_member_permissions,
+    utils::{create_embed_response, create_response, edit_response},
+};
+use rand::seq::SliceRandom;
+use serenity::{
+    all::{CommandDataOption, CommandDataOptionValue, CommandInteraction, CreateEmbedFooter},
+    builder::CreateEmbed,
+    client::Context,
+    model::id::UserId,
+    prelude::TypeMap,
+};
+use songbird::tracks::TrackHandle;
+use std::fmt::Write;
+use url::Url;
+
+const FAVORITES_PAGE_SIZE: usize = 10;
+
+pub async fn like(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let track = call
+        .lock()
+        .await
+        .queue()
+        .current()
+        .ok_or(ParrotError::NothingPlaying)?;
+
+    let title = like_track(ctx, interaction.user.id, &track).await?;
+    create_response(&ctx.http, interaction, ParrotMessage::Liked { title }).await
+}
+
+pub async fn favorites(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let subcommand = interaction.data.options.first().unwrap().clone();
+
+    let CommandDataOptionValue::SubCommand(args) = subcommand.value else {
+        unreachable!()
+    };
+
+    match subcommand.name.as_str() {
+        "play" => play(ctx, interaction, &args).await,
+        "list" => list(ctx, interaction, &args).await,
+        "remove" => remove(ctx, interaction, &args).await,
+        _ => unreachable!(),
+    }
+}
+
+/// Saves a track to someone's favorites. Returns the track's title.
+pub async fn like_track(
+    ctx: &Context,
+    user_id: UserId,
+    track: &TrackHandle,
+) -> Result<String, ParrotError> {
+    let mut data = ctx.data.write().await;
+
+    let favorite = data
+        .get::<MetadataStore>()
+        .unwrap()
+        .retrieve_metadata(&track.uuid().to_string())
+        .and_then(|metadata| {
+            Some(Favorite {
+                title: metadata.title.clone()?,
+                source_url: metadata.source_url.clone()?,
+            })
+        });
+    let favorite = verify(favorite, ParrotError::Other(FAIL_LIKE_UNAVAILABLE))?;
+    let title = favorite.title.clone();
+
+    let user_favorites = get_user_favorites(&mut data, user_id);
+    user_favorites.like(favorite)?;
+    user_favorites.save()?;
+
+    Ok(title)
+}
+
+/// Queues every favorite, in the order they were liked unless asked to shuffle them.
+async fn play(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    // favorites are queued like anything else, so they go by the same rules as /play
+    let member = interaction.member.as_deref();
+    check_member_permissions(ctx, guild_id, "play", member).await?;
+
+    let connection = {
+        let guild = ctx.cache.guild(guild_id).unwrap();
+        let bot_id = ctx.cache.current_user().id;
+        check_voice_connections(&guild, &interaction.user.id, &bot_id)
+    };
+    check_command_connection("play", connection)?;
+
+    let shuffle = args
+        .first()
+        .and_then(|arg| arg.value.as_bool())
+        .unwrap_or_default();
+
+    let mut tracks = {
+        let mut data = ctx.data.write().await;
+        get_user_favorites(&mut data, interaction.user.id)
+            .tracks
+            .clone()
+    };
+    verify(!tracks.is_empty(), ParrotError::Other(FAIL_FAVORITES_EMPTY))?;
+
+    if shuffle {
+        tracks.shuffle(&mut rand::thread_rng());
+    }
+
+    summon(ctx, interaction, false).await?;
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    // resolving every favorite takes a while, so let them know it's on its way
+    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
+
+    // favorites might have been liked somewhere that streams from more places than here
+    let (tracks, blocked): (Vec<Favorite>, Vec<Favorite>) = {
+        let data = ctx.data.read().await;
+        let default_settings = GuildSettings::new(guild_id);
+        let guild_settings = data
+            .get::<GuildSettingsMap>()
+            .unwrap()
+            .get(&guild_id)
+            .unwrap_or(&default_settings);
+
+        tracks.into_iter().partition(|favorite| {
+            Url::parse(&favorite.source_url)
+                .ok()
+                .and_then(|url| url.host_str().map(|host| host.to_string()))
+                .is_some_and(|domain| guild_settings.is_domain_allowed(&domain))
+        })
+    };
+
+    let query_types: Vec<QueryType> = tracks
+        .into_iter()
+        .map(|favorite| QueryType::from_source_url(favorite.source_url))
+        .collect();
+
+    let mut data = ctx.data.write().await;
+    let guild_stored_queue = data
+        .get_mut::<GuildStoredQueueMap>()
+        .unwrap()
+        .entry(guild_id)
+        .or_insert_with(GuildStoredQueue::new);
+
+    guild_stored_queue.queue.extend(query_types.iter().cloned());
+    guild_stored_queue.continue_play = true;
+    drop(data);
+
+    let mut failed = blocked.len();
+    for query_type in query_types.iter() {
+        let report = normal_query_type_resolver(
+            &call,
+            &ctx.http,
+            &ctx.data,
+            guild_id,
+            query_type,
+            Mode::End,
+            Some(interaction.user.id),
+        )
+        .await;
+
+        match report {
+            Ok(report) => failed += report.failed.len(),
+            Err(_) => failed += 1,
+        }
+    }
+
+    let count = query_types.len() + blocked.len() - failed;
+    edit_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::FavoritesQueued { count, failed },
+    )
+    .await?;
+
+    let queue = call.lock().await.queue().current_queue();
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+async fn list(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let tracks = {
+        let mut data = ctx.data.write().await;
+        get_user_favorites(&mut data, interaction.user.id)
+            .tracks
+            .clone()
+    };
+    verify(!tracks.is_empty(), ParrotError::Other(FAIL_FAVORITES_EMPTY))?;
+
+    let num_pages = tracks.len().div_ceil(FAVORITES_PAGE_SIZE);
+    let page = args
+        .first()
+        .and_then(|arg| arg.value.as_i64())
+        .map(|page| (page.max(1) as usize).min(num_pages))
+        .unwrap_or(1);
+
+    let embed = create_favorites_embed(&tracks, page, num_pages);
+    create_embed_response(&ctx.http, interaction, embed).await
+}
+
+async fn remove(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let index = args
+        .first()
+        .and_then(|arg| arg.value.as_i64())
+        .and_then(|index| usize::try_from(index).ok())
+        .unwrap_or_default();
+
+    let removed = {
+        let mut data = ctx.data.write().await;
+        let user_favorites = get_user_favorites(&mut data, interaction.user.id);
+
+        let removed = index
+            .checked_sub(1)
+            .and_then(|idx| user_favorites.remove(idx));
+        if removed.is_some() {
+            user_favorites.save()?;
+        }
+
+        removed
+    };
+
+    let removed = verify(removed, ParrotError::Other(FAIL_NO_FAVORITE_ON_INDEX))?;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::FavoriteRemoved {
+            title: removed.title,
+        },
+    )
+    .await
+}
+
+fn create_favorites_embed(tracks: &[Favorite], page: usize, num_pages: usize) -> CreateEmbed {
+    let start = (page - 1) * FAVORITES_PAGE_SIZE;
+    let mut description = String::new();
+
+    for (idx, favorite) in tracks
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(FAVORITES_PAGE_SIZE)
+    {
+        let _ = writeln!(
+            description,
+            "`{}.` [{}]({})",
+            idx + 1,
+            favorite.title,
+            favorite.source_url
+        );
+    }
+
+    CreateEmbed::default()
+        .title(FAVORITES_TITLE)
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "{} {} {} {}",
+            QUEUE_PAGE, page, QUEUE_PAGE_OF, num_pages
+        )))
+}
+
+/// Someone's favorites, read from disk the first time they're needed.
+fn get_user_favorites(data: &mut TypeMap, user_id: UserId) -> &mut UserFavorites {
+    data.get_mut::<UserFavoritesMap>()
+        .unwrap()
+        .entry(user_id)
+        .or_insert_with(|| {
+            let mut user_favorites = UserFavorites::new(user_id);
+
+            if let Err(err) = user_favorites.load_if_exists() {
+                println!(
+                    "[ERROR] Failed to load the favorites of user {} due to {}",
+                    user_id, err
+                );
+            }
+
+            user_favorites
+        })
+}
@@ -1,5 +1,5 @@
 use crate::{
-    errors::ParrotError, guild::stored_queue::GuildStoredQueueMap,
+    connection::disconnect, errors::ParrotError, guild::stored_queue::GuildStoredQueueMap,
     messaging::message::ParrotMessage, utils::create_response,
 };
 use serenity::{all::CommandInteraction, client::Context};
@@ -17,12 +17,7 @@ pub async fn leave(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
 
     drop(data);
 
-    let call = manager.get(guild_id).unwrap();
-    let mut handler = call.lock().await;
-    handler.remove_all_global_events();
-    drop(handler);
-
-    manager.remove(guild_id).await.unwrap();
+    disconnect(&manager, &ctx.data, guild_id).await?;
 
     create_response(&ctx.http, interaction, ParrotMessage::Leaving).await
 }
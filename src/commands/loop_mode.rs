@@ -0,0 +1,67 @@
+use crate::{
+    errors::{ParrotError, verify},
+    guild::settings::{GuildSettings, GuildSettingsMap, LoopMode},
+    messaging::{message::ParrotMessage, messages::FAIL_LOOP},
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context, model::id::GuildId};
+use std::str::FromStr;
+
+pub async fn loop_mode(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let mode = interaction
+        .data
+        .options
+        .first()
+        .and_then(|arg| arg.value.as_str())
+        .and_then(|mode| LoopMode::from_str(mode).ok())
+        .unwrap_or_default();
+
+    set_loop_mode(ctx, guild_id, mode).await?;
+    create_response(&ctx.http, interaction, ParrotMessage::Loop { mode }).await
+}
+
+/// Switches a guild over to a loop mode, starting with whatever's playing right now.
+pub async fn set_loop_mode(
+    ctx: &Context,
+    guild_id: GuildId,
+    mode: LoopMode,
+) -> Result<(), ParrotError> {
+    let manager = songbird::get(ctx).await.unwrap();
+    let current_track = match manager.get(guild_id) {
+        Some(call) => call.lock().await.queue().current(),
+        None => None,
+    };
+
+    // later tracks pick the mode up as they start playing
+    if let Some(track) = current_track {
+        let looped = match mode {
+            LoopMode::Track => track.enable_loop(),
+            LoopMode::Off | LoopMode::Queue => track.disable_loop(),
+        };
+        verify(looped, ParrotError::Other(FAIL_LOOP))?;
+    }
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.loop_mode = mode;
+    guild_settings.save()
+}
+
+/// The loop mode a guild is in, which is off for guilds that never picked one.
+pub async fn get_loop_mode(ctx: &Context, guild_id: GuildId) -> LoopMode {
+    let data = ctx.data.read().await;
+    data.get::<GuildSettingsMap>()
+        .unwrap()
+        .get(&guild_id)
+        .map(|guild_settings| guild_settings.loop_mode)
+        .unwrap_or_default()
+}
@@ -1,19 +1,27 @@
 pub mod autopause;
 pub mod clear;
+pub mod favorites;
 pub mod leave;
+pub mod loop_mode;
 pub mod manage_sources;
 pub mod now_playing;
 pub mod pause;
 pub mod play;
 pub mod queue;
+pub mod queue_cleanup;
+pub mod quiz;
 pub mod remove;
-pub mod repeat;
-pub mod repeat_queue;
 pub mod resume;
+pub mod schedule;
 pub mod seek;
+pub mod settings;
 pub mod shuffle;
 pub mod skip;
+pub mod sleep;
+pub mod spotify;
+pub mod stats;
 pub mod stop;
 pub mod summon;
+pub mod undo;
 pub mod version;
 pub mod voteskip;
@@ -1,8 +1,38 @@
 use crate::{
-    errors::ParrotError,
+    commands::{
+        favorites::like_track,
+        loop_mode::{get_loop_mode, set_loop_mode},
+        quiz::check_quiz_command,
+        shuffle::shuffle_upcoming,
+        skip::{force_skip_top_track, get_skip_message},
+        stop::stop_playback,
+        undo::save_undo_snapshot,
+    },
+    errors::{ParrotError, verify},
+    guild::settings::LoopMode,
+    handlers::{live_message::track_live_message, track_end::update_queue_messages},
+    messaging::{
+        message::ParrotMessage,
+        messages::{
+            NOW_PLAYING_LIKE, NOW_PLAYING_LOOP, NOW_PLAYING_LOOP_QUEUE, NOW_PLAYING_LOOP_TRACK,
+            NOW_PLAYING_PAUSE, NOW_PLAYING_RESUME, NOW_PLAYING_SHUFFLE, NOW_PLAYING_SKIP,
+            NOW_PLAYING_STOP,
+        },
+    },
+    permissions::check_component_permissions,
     utils::{create_embed_response, create_now_playing_embed},
 };
-use serenity::{all::CommandInteraction, client::Context};
+use serenity::{
+    all::{
+        ButtonStyle, CommandInteraction, ComponentInteraction, CreateActionRow, CreateButton,
+        CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+    client::Context,
+};
+use songbird::tracks::PlayMode;
+
+/// Every now playing button's id starts with this, which sets them apart from other components.
+pub const NOW_PLAYING_BUTTON_PREFIX: &str = "np_";
 
 pub async fn now_playing(
     ctx: &Context,
@@ -17,7 +47,204 @@ pub async fn now_playing(
         .queue()
         .current()
         .ok_or(ParrotError::NothingPlaying)?;
+    drop(handler);
 
     let embed = create_now_playing_embed(&track, &ctx.data).await?;
-    create_embed_response(&ctx.http, interaction, embed).await
+    create_embed_response(&ctx.http, interaction, embed).await?;
+
+    // keep the progress bar moving for a while
+    let message = interaction.get_response(&ctx.http).await?;
+    track_live_message(ctx, guild_id, message).await;
+    Ok(())
+}
+
+pub fn build_now_playing_btns(is_paused: bool, loop_mode: LoopMode) -> Vec<CreateActionRow> {
+    let (pause_label, pause_style) = match is_paused {
+        true => (NOW_PLAYING_RESUME, ButtonStyle::Success),
+        false => (NOW_PLAYING_PAUSE, ButtonStyle::Primary),
+    };
+
+    let (loop_label, loop_style) = match loop_mode {
+        LoopMode::Off => (NOW_PLAYING_LOOP, ButtonStyle::Secondary),
+        LoopMode::Track => (NOW_PLAYING_LOOP_TRACK, ButtonStyle::Success),
+        LoopMode::Queue => (NOW_PLAYING_LOOP_QUEUE, ButtonStyle::Success),
+    };
+
+    // a row only fits five buttons
+    let controls = CreateActionRow::Buttons(vec![
+        CreateButton::new("np_pause")
+            .label(pause_label)
+            .style(pause_style),
+        CreateButton::new("np_skip")
+            .label(NOW_PLAYING_SKIP)
+            .style(ButtonStyle::Primary),
+        CreateButton::new("np_loop")
+            .label(loop_label)
+            .style(loop_style),
+        CreateButton::new("np_shuffle")
+            .label(NOW_PLAYING_SHUFFLE)
+            .style(ButtonStyle::Secondary),
+        CreateButton::new("np_stop")
+            .label(NOW_PLAYING_STOP)
+            .style(ButtonStyle::Danger),
+    ]);
+
+    let like = CreateActionRow::Buttons(vec![
+        CreateButton::new("np_like")
+            .label(NOW_PLAYING_LIKE)
+            .style(ButtonStyle::Secondary),
+    ]);
+
+    vec![controls, like]
+}
+
+/// The command a now playing button stands in for, which decides who may press it.
+pub fn get_button_command(custom_id: &str, is_paused: bool) -> Option<&'static str> {
+    match custom_id {
+        "np_pause" if is_paused => Some("resume"),
+        "np_pause" => Some("pause"),
+        "np_skip" => Some("skip"),
+        "np_loop" => Some("loop"),
+        "np_shuffle" => Some("shuffle"),
+        "np_stop" => Some("stop"),
+        "np_like" => Some("like"),
+        _ => None,
+    }
+}
+
+pub async fn now_playing_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).ok_or(ParrotError::NotConnected)?;
+
+    let track = call
+        .lock()
+        .await
+        .queue()
+        .current()
+        .ok_or(ParrotError::NothingPlaying)?;
+
+    let state = track
+        .get_info()
+        .await
+        .map_err(|_| ParrotError::NothingPlaying)?;
+
+    let is_paused = matches!(state.playing, PlayMode::Pause);
+
+    let Some(command_name) = get_button_command(&interaction.data.custom_id, is_paused) else {
+        return Ok(());
+    };
+
+    // requesters may skip their own track, just like with /skip
+    let owned = (command_name == "skip").then_some((0, 1));
+    check_component_permissions(ctx, interaction, command_name, owned).await?;
+    check_quiz_command(&ctx.data, guild_id, command_name).await?;
+
+    // liking is personal, so it leaves the message as it is for everyone else
+    if command_name == "like" {
+        let title = like_track(ctx, interaction.user.id, &track).await?;
+        let embed =
+            CreateEmbed::default().description(format!("{}", ParrotMessage::Liked { title }));
+
+        let message = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true);
+
+        return interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+            .await
+            .map_err(Into::into);
+    }
+
+    if command_name == "stop" {
+        let queue = stop_playback(ctx, guild_id, &call).await?;
+        update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+
+        let embed = CreateEmbed::default().description(format!("{}", ParrotMessage::Stop));
+        return respond_with(ctx, interaction, embed, vec![]).await;
+    }
+
+    // each press moves on to the next mode, which is settled before the call gets locked
+    let loop_mode = match command_name {
+        "loop" => {
+            let loop_mode = get_loop_mode(ctx, guild_id).await.next();
+            set_loop_mode(ctx, guild_id, loop_mode).await?;
+            loop_mode
+        }
+        _ => get_loop_mode(ctx, guild_id).await,
+    };
+
+    let handler = call.lock().await;
+
+    // the track's state lags behind, so work out what the buttons should show from here
+    let is_paused = match command_name {
+        "pause" => {
+            verify(
+                handler.queue().pause(),
+                ParrotError::Other("Failed to pause"),
+            )?;
+            true
+        }
+        "resume" => {
+            verify(
+                handler.queue().resume(),
+                ParrotError::Other("Failed to resume"),
+            )?;
+            false
+        }
+        "shuffle" => {
+            let queue = handler.queue().current_queue();
+            save_undo_snapshot(&ctx.data, guild_id, "shuffle", &queue, 0..0, false).await;
+            shuffle_upcoming(handler.queue());
+            is_paused
+        }
+        "skip" => {
+            force_skip_top_track(&handler).await?;
+            false
+        }
+        _ => is_paused,
+    };
+
+    let queue = handler.queue().current_queue();
+    let embed = match queue.first() {
+        Some(track) => Some(create_now_playing_embed(track, &ctx.data).await?),
+        None => None,
+    };
+
+    let (embed, buttons) = match embed {
+        Some(embed) => (embed, build_now_playing_btns(is_paused, loop_mode)),
+        None => {
+            let outcome = get_skip_message(ctx, &handler, 1).await?;
+            (
+                CreateEmbed::default().description(format!("{outcome}")),
+                vec![],
+            )
+        }
+    };
+    drop(handler);
+
+    if matches!(command_name, "shuffle" | "skip") {
+        update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    }
+
+    respond_with(ctx, interaction, embed, buttons).await
+}
+
+async fn respond_with(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    embed: CreateEmbed,
+    buttons: Vec<CreateActionRow>,
+) -> Result<(), ParrotError> {
+    let message = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(buttons);
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+        .await
+        .map_err(Into::into)
 }
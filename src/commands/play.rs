@@ -4,39 +4,54 @@ use crate::{
     guild::{
         http_client::HttpClientInstance,
         metadata_store::MetadataStore,
-        settings::{GuildSettings, GuildSettingsMap},
+        settings::{DuplicatePolicy, GuildSettings, GuildSettingsMap, TrackFallback},
         stored_queue::{GuildStoredQueue, GuildStoredQueueMap},
     },
     handlers::track_end::update_queue_messages,
     messaging::{
         message::ParrotMessage,
-        messages::{PLAY_QUEUE, PLAY_TOP, SPOTIFY_AUTH_FAILED, TRACK_DURATION, TRACK_TIME_TO_PLAY},
+        messages::{
+            PLAY_DUPLICATES_MORE, PLAY_DUPLICATES_QUEUED, PLAY_DUPLICATES_REFUSED,
+            PLAY_FAILED_TRACKS, PLAY_FAILED_TRACKS_MORE, PLAY_QUEUE, PLAY_TOP,
+            SOURCE_INVALID_QUERY, TRACK_DURATION, TRACK_TIME_TO_PLAY,
+        },
     },
-    sources::spotify::{SPOTIFY, Spotify},
+    sources::{Source, SourceRegistry, direct::DirectLink},
     utils::{
-        compare_domains, create_now_playing_embed, create_response, edit_embed_response,
-        edit_response, get_human_readable_timestamp, get_track_metadata,
+        create_now_playing_embed, create_response, edit_embed_response, edit_response,
+        get_human_readable_timestamp, get_track_metadata,
     },
 };
+use lazy_static::lazy_static;
+use regex::Regex;
 use serenity::{
-    all::{CommandInteraction, CreateEmbedFooter},
+    all::{CommandInteraction, CreateEmbedFooter, CreateInteractionResponseFollowup},
     builder::CreateEmbed,
     client::Context,
     http::Http,
-    model::id::GuildId,
+    model::id::{GuildId, UserId},
     prelude::{Mutex, TypeMap},
 };
 use songbird::{
     Call,
-    input::{AuxMetadata, Compose, YoutubeDl},
+    input::{AuxMetadata, Compose, HttpRequest, Input, YoutubeDl},
     tracks::TrackHandle,
 };
-use std::{cmp::Ordering, error::Error as StdError, sync::Arc, time::Duration};
+use std::{cmp::Ordering, error::Error as StdError, fmt::Write, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
 use url::Url;
 
 use reqwest;
 
+const ALTERNATE_SEARCH_PREFIX: &str = "scsearch1:";
+const FAILED_TRACKS_LISTED: usize = 15;
+
+lazy_static! {
+    static ref BRACKETS_REGEX: Regex = Regex::new(r"\([^)]*\)|\[[^\]]*\]").unwrap();
+    static ref FEATURING_REGEX: Regex =
+        Regex::new(r"(?i)\s+(feat\.?|ft\.?|featuring)\s+[^-(\[]*[^-(\[\s]").unwrap();
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum Mode {
     End,
@@ -47,12 +62,68 @@ pub enum Mode {
     Jump,
 }
 
+/// How tracks should be queued on behalf of a request.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EnqueueOptions {
+    pub fallback: TrackFallback,
+    pub duplicates: DuplicatePolicy,
+    pub requester: Option<UserId>,
+}
+
+/// Tracks that couldn't be queued, or were already queued, while resolving a request.
+#[derive(Debug, Default)]
+pub struct EnqueueReport {
+    pub failed: Vec<QueryType>,
+    pub duplicates: Vec<String>,
+}
+
+impl EnqueueReport {
+    /// Keeps track of a query that didn't make it into the queue.
+    /// Refused duplicates are already listed as such, so they don't count as failures.
+    pub fn record_failure(&mut self, query_type: QueryType, err: &ParrotError) {
+        if !matches!(err, ParrotError::DuplicateTrack(_)) {
+            self.failed.push(query_type);
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum QueryType {
     Keywords(String),
     KeywordList(Vec<String>),
     VideoLink(String),
     PlaylistLink(String),
+    DirectLink(String),
+}
+
+impl QueryType {
+    /// Folds the per-track queries of a resolved link into a single query,
+    /// so that it can go through the same flow as any other request.
+    pub fn merge(mut queries: Vec<QueryType>) -> Option<QueryType> {
+        if queries.len() <= 1 {
+            return queries.pop();
+        }
+
+        queries
+            .into_iter()
+            .map(|query| match query {
+                QueryType::Keywords(keywords) => Some(vec![keywords]),
+                QueryType::KeywordList(keywords_list) => Some(keywords_list),
+                QueryType::VideoLink(_) | QueryType::PlaylistLink(_) | QueryType::DirectLink(_) => {
+                    None
+                }
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(|keywords| QueryType::KeywordList(keywords.concat()))
+    }
+
+    /// The query that loads a queued track again from the link in its metadata.
+    pub fn from_source_url(source_url: String) -> QueryType {
+        match Url::parse(&source_url) {
+            Ok(url) if DirectLink.matches(&url) => QueryType::DirectLink(source_url),
+            _ => QueryType::VideoLink(source_url),
+        }
+    }
 }
 
 pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
@@ -80,74 +151,34 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
     summon(ctx, interaction, false).await?;
     let call = manager.get(guild_id).unwrap();
 
-    // determine whether this is a link or a query string
-    let query_type = match Url::parse(url) {
-        Ok(url_data) => match url_data.host_str() {
-            Some("open.spotify.com") => {
-                let spotify = SPOTIFY.lock().await;
-                let spotify = verify(spotify.as_ref(), ParrotError::Other(SPOTIFY_AUTH_FAILED))?;
-                Some(Spotify::extract(spotify, url).await?)
-            }
-            Some(other) => {
-                let mut data = ctx.data.write().await;
-                let settings = data.get_mut::<GuildSettingsMap>().unwrap();
-                let guild_settings = settings
-                    .entry(guild_id)
-                    .or_insert_with(|| GuildSettings::new(guild_id));
-
-                let is_allowed = guild_settings
-                    .allowed_domains
-                    .iter()
-                    .any(|d| compare_domains(d, other));
-
-                let is_banned = guild_settings
-                    .banned_domains
-                    .iter()
-                    .any(|d| compare_domains(d, other));
-
-                if is_banned || (guild_settings.banned_domains.is_empty() && !is_allowed) {
-                    return create_response(
-                        &ctx.http,
-                        interaction,
-                        ParrotMessage::PlayDomainBanned {
-                            domain: other.to_string(),
-                        },
-                    )
-                    .await;
-                }
+    let http_client = {
+        let data = ctx.data.read().await;
+        &data.get::<HttpClientInstance>().unwrap().clone()
+    };
 
-                if url.contains("list=") {
-                    Some(QueryType::PlaylistLink(url.to_string()))
-                } else {
-                    Some(QueryType::VideoLink(url.to_string()))
-                }
-            }
-            None => None,
-        },
-        Err(_) => {
-            let mut data = ctx.data.write().await;
-            let settings = data.get_mut::<GuildSettingsMap>().unwrap();
-            let guild_settings = settings
-                .entry(guild_id)
-                .or_insert_with(|| GuildSettings::new(guild_id));
-
-            if guild_settings.banned_domains.contains("youtube.com")
-                || (guild_settings.banned_domains.is_empty()
-                    && !guild_settings.allowed_domains.contains("youtube.com"))
-            {
-                return create_response(
-                    &ctx.http,
-                    interaction,
-                    ParrotMessage::PlayDomainBanned {
-                        domain: "youtube.com".to_string(),
-                    },
-                )
-                .await;
-            }
+    // reply with a temporary message while we fetch the source
+    // needed because interactions must be replied within 3s and resolving links takes longer
+    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
 
-            Some(QueryType::Keywords(url.to_string()))
+    let (query_type, domain) = get_query_type(&ctx.data, http_client, url).await?;
+
+    if let Some(domain) = domain {
+        let mut data = ctx.data.write().await;
+        let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+        let guild_settings = settings
+            .entry(guild_id)
+            .or_insert_with(|| GuildSettings::new(guild_id));
+
+        if !guild_settings.is_domain_allowed(&domain) {
+            drop(data);
+            return create_response(
+                &ctx.http,
+                interaction,
+                ParrotMessage::PlayDomainBanned { domain },
+            )
+            .await;
         }
-    };
+    }
 
     let query_type = verify(
         query_type,
@@ -164,64 +195,109 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
     guild_stored_queue.continue_play = true;
     drop(data);
 
-    let http_client = {
-        let data = ctx.data.read().await;
-        &data.get::<HttpClientInstance>().unwrap().clone()
-    };
-
-    // reply with a temporary message while we fetch the source
-    // needed because interactions must be replied within 3s and queueing takes longer
-    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
-
     let handler = call.lock().await;
     let queue_was_empty = handler.queue().is_empty();
     drop(handler);
 
+    let options = EnqueueOptions {
+        fallback: get_track_fallback(&ctx.data, guild_id).await,
+        duplicates: get_duplicate_policy(&ctx.data, guild_id).await,
+        requester: Some(interaction.user.id),
+    };
+    let mut report = EnqueueReport::default();
+
     match mode {
         Mode::End => {
-            normal_query_type_resolver(&call, &ctx.http, &ctx.data, guild_id, &query_type, mode)
-                .await?
+            report = resolve_query_type(
+                &call,
+                &ctx.http,
+                &ctx.data,
+                guild_id,
+                &query_type,
+                mode,
+                options,
+            )
+            .await?
         }
         Mode::Next => match query_type.clone() {
-            QueryType::Keywords(_) | QueryType::VideoLink(_) => {
-                let queue = insert_track(&call, http_client, &query_type, 1, &ctx.data).await?;
+            QueryType::Keywords(_) | QueryType::VideoLink(_) | QueryType::DirectLink(_) => {
+                let queue = insert_track(
+                    &call,
+                    http_client,
+                    &query_type,
+                    1,
+                    &ctx.data,
+                    options,
+                    &mut report,
+                )
+                .await?;
                 update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
             }
             QueryType::PlaylistLink(url) => {
                 let urls = get_urls_from_playlist(http_client, url, None).await?;
+                let mut insert_idx = 1;
 
-                for (idx, url) in urls.into_iter().flatten().enumerate() {
-                    let Ok(queue) = insert_track(
+                for url in urls.into_iter().flatten() {
+                    let query_type = QueryType::VideoLink(url);
+                    let queue = match insert_track(
                         &call,
                         http_client,
-                        &QueryType::VideoLink(url),
-                        idx + 1,
+                        &query_type,
+                        insert_idx,
                         &ctx.data,
+                        options,
+                        &mut report,
                     )
                     .await
-                    else {
-                        continue;
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
                     };
+                    insert_idx += 1;
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
             QueryType::KeywordList(keywords_list) => {
-                for (idx, keywords) in keywords_list.into_iter().enumerate() {
-                    let queue = insert_track(
+                let mut insert_idx = 1;
+
+                for keywords in keywords_list.into_iter() {
+                    let query_type = QueryType::Keywords(keywords);
+                    let queue = match insert_track(
                         &call,
                         http_client,
-                        &QueryType::Keywords(keywords),
-                        idx + 1,
+                        &query_type,
+                        insert_idx,
                         &ctx.data,
+                        options,
+                        &mut report,
                     )
-                    .await?;
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
+                    };
+                    insert_idx += 1;
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
         },
         Mode::Jump => match query_type.clone() {
-            QueryType::Keywords(_) | QueryType::VideoLink(_) => {
-                let mut queue = enqueue_track(&call, http_client, &query_type, &ctx.data).await?;
+            QueryType::Keywords(_) | QueryType::VideoLink(_) | QueryType::DirectLink(_) => {
+                let mut queue = enqueue_track(
+                    &call,
+                    http_client,
+                    &query_type,
+                    &ctx.data,
+                    options,
+                    &mut report,
+                )
+                .await?;
 
                 if !queue_was_empty {
                     rotate_tracks(&call, 1).await.ok();
@@ -235,20 +311,31 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
 
                 let mut insert_idx = 1;
 
-                for (i, url) in urls.into_iter().flatten().enumerate() {
-                    let Ok(mut queue) = insert_track(
+                let mut skipped = queue_was_empty;
+
+                for url in urls.into_iter().flatten() {
+                    let query_type = QueryType::VideoLink(url);
+                    let mut queue = match insert_track(
                         &call,
                         http_client,
-                        &QueryType::VideoLink(url),
+                        &query_type,
                         insert_idx,
                         &ctx.data,
+                        options,
+                        &mut report,
                     )
                     .await
-                    else {
-                        continue;
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
                     };
 
-                    if i == 0 && !queue_was_empty {
+                    // skip the current track once the first new one is in place
+                    if !skipped {
+                        skipped = true;
                         queue = force_skip_top_track(&call.lock().await).await?;
                     } else {
                         insert_idx += 1;
@@ -260,17 +347,30 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
             QueryType::KeywordList(keywords_list) => {
                 let mut insert_idx = 1;
 
-                for (i, keywords) in keywords_list.into_iter().enumerate() {
-                    let mut queue = insert_track(
+                let mut skipped = queue_was_empty;
+
+                for keywords in keywords_list.into_iter() {
+                    let query_type = QueryType::Keywords(keywords);
+                    let mut queue = match insert_track(
                         &call,
                         http_client,
-                        &QueryType::Keywords(keywords),
+                        &query_type,
                         insert_idx,
                         &ctx.data,
+                        options,
+                        &mut report,
                     )
-                    .await?;
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
+                    };
 
-                    if i == 0 && !queue_was_empty {
+                    if !skipped {
+                        skipped = true;
                         queue = force_skip_top_track(&call.lock().await).await?;
                     } else {
                         insert_idx += 1;
@@ -285,24 +385,45 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
                 let urls = get_urls_from_playlist(http_client, url, None).await?;
 
                 for url in urls.into_iter().flatten() {
-                    let Ok(queue) =
-                        enqueue_track(&call, http_client, &QueryType::VideoLink(url), &ctx.data)
-                            .await
-                    else {
-                        continue;
+                    let query_type = QueryType::VideoLink(url);
+                    let queue = match enqueue_track(
+                        &call,
+                        http_client,
+                        &query_type,
+                        &ctx.data,
+                        options,
+                        &mut report,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
                     };
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
             QueryType::KeywordList(keywords_list) => {
                 for keywords in keywords_list.into_iter() {
-                    let queue = enqueue_track(
+                    let query_type = QueryType::Keywords(keywords);
+                    let queue = match enqueue_track(
                         &call,
                         http_client,
-                        &QueryType::Keywords(keywords),
+                        &query_type,
                         &ctx.data,
+                        options,
+                        &mut report,
                     )
-                    .await?;
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
+                    };
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
@@ -324,14 +445,20 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
             let estimated_time = calculate_time_until_play(&queue, mode).await.unwrap();
 
             match (query_type, mode) {
-                (QueryType::VideoLink(_) | QueryType::Keywords(_), Mode::Next) => {
+                (
+                    QueryType::VideoLink(_) | QueryType::Keywords(_) | QueryType::DirectLink(_),
+                    Mode::Next,
+                ) => {
                     let track = queue.first().unwrap();
                     let embed =
                         create_queued_embed(PLAY_TOP, track, estimated_time, &ctx.data).await?;
 
                     edit_embed_response(&ctx.http, interaction, embed).await?;
                 }
-                (QueryType::VideoLink(_) | QueryType::Keywords(_), Mode::End) => {
+                (
+                    QueryType::VideoLink(_) | QueryType::Keywords(_) | QueryType::DirectLink(_),
+                    Mode::End,
+                ) => {
                     let track = queue.last().unwrap();
                     let embed =
                         create_queued_embed(PLAY_QUEUE, track, estimated_time, &ctx.data).await?;
@@ -353,6 +480,26 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
         _ => println!("Ignore queue reordering"),
     }
 
+    if !report.failed.is_empty() {
+        let embed = create_failed_tracks_embed(&report);
+        interaction
+            .create_followup(
+                &ctx.http,
+                CreateInteractionResponseFollowup::new().embed(embed),
+            )
+            .await?;
+    }
+
+    if !report.duplicates.is_empty() {
+        let embed = create_duplicate_tracks_embed(&report, options.duplicates);
+        interaction
+            .create_followup(
+                &ctx.http,
+                CreateInteractionResponseFollowup::new().embed(embed),
+            )
+            .await?;
+    }
+
     Ok(())
 }
 
@@ -395,6 +542,49 @@ async fn calculate_time_until_play(queue: &[TrackHandle], mode: Mode) -> Option<
     }
 }
 
+fn create_failed_tracks_embed(report: &EnqueueReport) -> CreateEmbed {
+    let mut description = String::new();
+
+    for query_type in report.failed.iter().take(FAILED_TRACKS_LISTED) {
+        let _ = match query_type {
+            QueryType::Keywords(keywords) => writeln!(description, "• {}", keywords),
+            QueryType::VideoLink(url) | QueryType::DirectLink(url) => {
+                writeln!(description, "• <{}>", url)
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let remaining = report.failed.len().saturating_sub(FAILED_TRACKS_LISTED);
+    if remaining > 0 {
+        let _ = writeln!(description, "{} {}", remaining, PLAY_FAILED_TRACKS_MORE);
+    }
+
+    CreateEmbed::default()
+        .title(PLAY_FAILED_TRACKS)
+        .description(description)
+}
+
+fn create_duplicate_tracks_embed(report: &EnqueueReport, policy: DuplicatePolicy) -> CreateEmbed {
+    let mut description = String::new();
+
+    for title in report.duplicates.iter().take(FAILED_TRACKS_LISTED) {
+        let _ = writeln!(description, "• {}", title);
+    }
+
+    let remaining = report.duplicates.len().saturating_sub(FAILED_TRACKS_LISTED);
+    if remaining > 0 {
+        let _ = writeln!(description, "{} {}", remaining, PLAY_DUPLICATES_MORE);
+    }
+
+    let title = match policy {
+        DuplicatePolicy::Refuse => PLAY_DUPLICATES_REFUSED,
+        _ => PLAY_DUPLICATES_QUEUED,
+    };
+
+    CreateEmbed::default().title(title).description(description)
+}
+
 async fn create_queued_embed(
     title: &str,
     track: &TrackHandle,
@@ -404,7 +594,7 @@ async fn create_queued_embed(
     let mut embed = CreateEmbed::default();
     let metadata = get_track_metadata(track, data).await?;
 
-    embed = embed.thumbnail(metadata.thumbnail.unwrap()).field(
+    embed = embed.field(
         title,
         format!(
             "[**{}**]({})",
@@ -414,6 +604,10 @@ async fn create_queued_embed(
         false,
     );
 
+    if let Some(thumbnail) = metadata.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
     let footer_text = format!(
         "{}{}\n{}{}",
         TRACK_DURATION,
@@ -425,13 +619,151 @@ async fn create_queued_embed(
     Ok(embed.footer(CreateEmbedFooter::new(footer_text)))
 }
 
-fn get_track_source<'a>(http_client: reqwest::Client, query_type: QueryType) -> YoutubeDl<'a> {
-    match query_type {
-        QueryType::VideoLink(url) => YoutubeDl::new(http_client, url),
+/// Works out what to queue for a link or search, along with the domain it streams from
+/// so it can be checked against the guild's allowed and banned domains.
+pub async fn get_query_type(
+    data: &Arc<RwLock<TypeMap>>,
+    http_client: &reqwest::Client,
+    query: &str,
+) -> Result<(Option<QueryType>, Option<String>), ParrotError> {
+    // determine whether this is a link or a query string
+    match Url::parse(query) {
+        Ok(url_data) => {
+            let registry = {
+                let data = data.read().await;
+                data.get::<SourceRegistry>().unwrap().clone()
+            };
+
+            let source = verify(
+                registry.find(&url_data),
+                ParrotError::Other(SOURCE_INVALID_QUERY),
+            )?;
+
+            // sources that only look tracks up elsewhere don't stream from the link's domain
+            let domain = match source.metadata().streams_from_origin {
+                true => url_data.host_str().map(|host| host.to_string()),
+                false => None,
+            };
+
+            let query_type = QueryType::merge(source.resolve(http_client, &url_data).await?);
+            Ok((query_type, domain))
+        }
+        Err(_) => Ok((
+            Some(QueryType::Keywords(query.to_string())),
+            Some("youtube.com".to_string()),
+        )),
+    }
+}
 
+pub async fn get_track_source(
+    http_client: reqwest::Client,
+    query_type: QueryType,
+) -> Result<(Input, AuxMetadata), ParrotError> {
+    let mut source = match query_type {
+        QueryType::VideoLink(url) => YoutubeDl::new(http_client, url),
         QueryType::Keywords(query) => YoutubeDl::new_search(http_client, query),
+        QueryType::DirectLink(url) => {
+            let metadata = DirectLink::build_metadata(&url);
+            return Ok((HttpRequest::new(http_client, url).into(), metadata));
+        }
         _ => unreachable!(),
+    };
+
+    let aux_metadata = source.aux_metadata().await.map_err(|err| {
+        println!("{:?}", err);
+
+        ParrotError::Other("Unable to get AuxMetadata")
+    })?;
+
+    Ok((source.into(), aux_metadata))
+}
+
+/// Builds a track from the link of one that already played. Its metadata is known by now,
+/// so the source is left to load whenever the track comes up.
+pub fn get_replay_source(http_client: reqwest::Client, source_url: String) -> Input {
+    match QueryType::from_source_url(source_url) {
+        QueryType::DirectLink(url) => HttpRequest::new(http_client, url).into(),
+        QueryType::VideoLink(url) => YoutubeDl::new(http_client, url).into(),
+        _ => unreachable!(),
+    }
+}
+
+/// Alternative queries to try, in order, when the given one can't be loaded.
+/// Only searches have fallbacks, since a broken link stays broken no matter how it's asked for.
+pub fn get_fallback_queries(query_type: &QueryType, fallback: TrackFallback) -> Vec<QueryType> {
+    let QueryType::Keywords(keywords) = query_type else {
+        return Vec::new();
+    };
+
+    let mut queries = Vec::new();
+
+    if matches!(fallback, TrackFallback::Requery | TrackFallback::Both) {
+        queries.extend(
+            get_requery_shapes(keywords)
+                .into_iter()
+                .map(QueryType::Keywords),
+        );
+    }
+
+    if matches!(
+        fallback,
+        TrackFallback::AlternateSource | TrackFallback::Both
+    ) {
+        // yt-dlp treats search prefixes as links, so this goes through the regular link path
+        queries.push(QueryType::VideoLink(format!(
+            "{}{}",
+            ALTERNATE_SEARCH_PREFIX, keywords
+        )));
     }
+
+    queries
+}
+
+/// Rewrites a search into simpler shapes, e.g. without featured artists, remaster tags
+/// or the artist name altogether, which often match when the full query doesn't.
+pub fn get_requery_shapes(keywords: &str) -> Vec<String> {
+    let simplified = FEATURING_REGEX.replace_all(keywords, "");
+    let simplified = BRACKETS_REGEX.replace_all(&simplified, "");
+    let simplified = simplified.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let title_only = simplified
+        .split_once(" - ")
+        .map(|(_, title)| title.trim().to_string());
+
+    let mut shapes: Vec<String> = Vec::new();
+
+    for shape in std::iter::once(simplified).chain(title_only) {
+        if !shape.is_empty() && shape != keywords && !shapes.contains(&shape) {
+            shapes.push(shape);
+        }
+    }
+
+    shapes
+}
+
+async fn get_track_fallback(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> TrackFallback {
+    let data = data.read().await;
+    let default_settings = GuildSettings::new(guild_id);
+    data.get::<GuildSettingsMap>()
+        .and_then(|settings| settings.get(&guild_id))
+        .unwrap_or(&default_settings)
+        .get_track_fallback()
+}
+
+async fn get_duplicate_policy(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> DuplicatePolicy {
+    let data = data.read().await;
+    data.get::<GuildSettingsMap>()
+        .and_then(|settings| settings.get(&guild_id))
+        .map(|guild_settings| guild_settings.duplicate_policy)
+        .unwrap_or_default()
+}
+
+/// Whether a track loaded from the given link is already waiting in, or playing from, the queue.
+pub fn is_already_queued<'a>(
+    source_url: &str,
+    mut queued: impl Iterator<Item = &'a AuxMetadata>,
+) -> bool {
+    queued.any(|metadata| metadata.source_url.as_deref() == Some(source_url))
 }
 
 async fn enqueue_track(
@@ -439,23 +771,59 @@ async fn enqueue_track(
     http_client: &reqwest::Client,
     query_type: &QueryType,
     data: &Arc<RwLock<TypeMap>>,
+    options: EnqueueOptions,
+    report: &mut EnqueueReport,
 ) -> Result<Vec<TrackHandle>, ParrotError> {
-    let source = get_track_source(http_client.clone(), query_type.clone());
+    let mut source = get_track_source(http_client.clone(), query_type.clone()).await;
+    let mut fallback_queries = get_fallback_queries(query_type, options.fallback).into_iter();
+
+    // keep the original error around in case none of the fallbacks work either
+    while source.is_err()
+        && let Some(fallback_query) = fallback_queries.next()
+    {
+        println!("[INFO] retrying {:?} as {:?}", query_type, fallback_query);
+        if let Ok(fallback_source) = get_track_source(http_client.clone(), fallback_query).await {
+            source = Ok(fallback_source);
+        }
+    }
 
-    let mut source_c = source.clone();
-    let mut handler = call.lock().await;
-    let track_handle = handler.enqueue_input(source.clone().into()).await;
+    let (input, aux_metadata) = source?;
 
-    let aux_metadata = source_c.aux_metadata().await.map_err(|err| {
-        println!("{:?}", err);
+    if options.duplicates != DuplicatePolicy::Allow
+        && let Some(source_url) = &aux_metadata.source_url
+    {
+        let queue = call.lock().await.queue().current_queue();
+        let ctx_data = data.read().await;
+        let metadata_store = ctx_data.get::<MetadataStore>().unwrap();
 
-        ParrotError::Other("Unable to get AuxMetadata")
-    })?;
+        let queued = queue
+            .iter()
+            .filter_map(|track| metadata_store.retrieve_metadata(&track.uuid().to_string()));
+
+        if is_already_queued(source_url, queued) {
+            let title = aux_metadata
+                .title
+                .clone()
+                .unwrap_or_else(|| source_url.clone());
+            report.duplicates.push(title.clone());
+
+            if options.duplicates == DuplicatePolicy::Refuse {
+                return Err(ParrotError::DuplicateTrack(title));
+            }
+        }
+    }
+
+    let mut handler = call.lock().await;
+    let track_handle = handler.enqueue_input(input).await;
 
     if let Some(title) = &aux_metadata.title {
         let mut ctx_data = data.write().await;
         let metadata_store = ctx_data.get_mut::<MetadataStore>().unwrap();
         metadata_store.insert_metadata(track_handle.uuid().to_string(), aux_metadata.clone());
+
+        if let Some(requester) = options.requester {
+            metadata_store.insert_requester(track_handle.uuid().to_string(), requester);
+        }
         drop(ctx_data);
         println!("[INFO] queueing {}", title);
     }
@@ -469,13 +837,15 @@ async fn insert_track(
     query_type: &QueryType,
     idx: usize,
     data: &Arc<RwLock<TypeMap>>,
+    options: EnqueueOptions,
+    report: &mut EnqueueReport,
 ) -> Result<Vec<TrackHandle>, ParrotError> {
     let handler = call.lock().await;
     let queue_size = handler.queue().len();
     drop(handler);
 
     if queue_size <= 1 {
-        let queue = enqueue_track(call, http_client, query_type, data).await?;
+        let queue = enqueue_track(call, http_client, query_type, data, options, report).await?;
         return Ok(queue);
     }
 
@@ -484,7 +854,7 @@ async fn insert_track(
         ParrotError::NotInRange("index", idx as isize, 1, queue_size as isize),
     )?;
 
-    enqueue_track(call, http_client, query_type, data).await?;
+    enqueue_track(call, http_client, query_type, data, options, report).await?;
 
     let handler = call.lock().await;
     handler.queue().modify_queue(|queue| {
@@ -522,50 +892,121 @@ pub async fn normal_query_type_resolver(
     guild_id: GuildId,
     query_type: &QueryType,
     mode: Mode,
-) -> Result<(), ParrotError> {
+    requester: Option<UserId>,
+) -> Result<EnqueueReport, ParrotError> {
+    // tracks queued on parrot's own accord were already let in once, so let them be
+    let options = EnqueueOptions {
+        fallback: get_track_fallback(data, guild_id).await,
+        duplicates: DuplicatePolicy::Allow,
+        requester,
+    };
+
+    resolve_query_type(call, http, data, guild_id, query_type, mode, options).await
+}
+
+async fn resolve_query_type(
+    call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    query_type: &QueryType,
+    mode: Mode,
+    options: EnqueueOptions,
+) -> Result<EnqueueReport, ParrotError> {
     let http_client = {
         let data_instance = data.read().await;
         &data_instance.get::<HttpClientInstance>().unwrap().clone()
     };
 
+    let mut report = EnqueueReport::default();
+
     match query_type.clone() {
-        QueryType::Keywords(_) | QueryType::VideoLink(_) => {
-            let queue = enqueue_track(call, http_client, query_type, data).await?;
+        QueryType::Keywords(_) | QueryType::VideoLink(_) | QueryType::DirectLink(_) => {
+            let queue =
+                enqueue_track(call, http_client, query_type, data, options, &mut report).await?;
             update_queue_messages(http, data, &queue, guild_id).await;
-            Ok(())
         }
         QueryType::PlaylistLink(url) => {
             let urls = get_urls_from_playlist(http_client, url, Some(mode)).await?;
 
             for url in urls.iter().filter_map(|v| v.clone()) {
-                let Ok(queue) = enqueue_track(
-                    call,
-                    http_client,
-                    &QueryType::VideoLink(url.to_string()),
-                    data,
-                )
-                .await
-                else {
-                    continue;
-                };
+                let query_type = QueryType::VideoLink(url.to_string());
+                let queue =
+                    match enqueue_track(call, http_client, &query_type, data, options, &mut report)
+                        .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
+                    };
                 update_queue_messages(http, data, &queue, guild_id).await;
             }
-            Ok(())
         }
         QueryType::KeywordList(keywords_list) => {
             for keywords in keywords_list.iter() {
-                let queue = enqueue_track(
-                    call,
-                    http_client,
-                    &QueryType::Keywords(keywords.to_string()),
-                    data,
-                )
-                .await?;
+                let query_type = QueryType::Keywords(keywords.to_string());
+                let queue =
+                    match enqueue_track(call, http_client, &query_type, data, options, &mut report)
+                        .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => {
+                            report.record_failure(query_type, &err);
+                            continue;
+                        }
+                    };
                 update_queue_messages(http, data, &queue, guild_id).await;
             }
-            Ok(())
         }
     }
+
+    Ok(report)
+}
+
+/// Queues the playlist or station a guild falls back to in 24/7 mode, if it has one.
+pub async fn play_fallback_playlist(
+    call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+) -> Result<(), ParrotError> {
+    let (fallback_playlist, http_client) = {
+        let data = data.read().await;
+        let fallback_playlist = data
+            .get::<GuildSettingsMap>()
+            .unwrap()
+            .get(&guild_id)
+            .filter(|guild_settings| guild_settings.always_on)
+            .and_then(|guild_settings| guild_settings.fallback_playlist.clone());
+
+        (
+            fallback_playlist,
+            data.get::<HttpClientInstance>().unwrap().clone(),
+        )
+    };
+
+    let Some(fallback_playlist) = fallback_playlist else {
+        return Ok(());
+    };
+
+    let (query_type, _) = get_query_type(data, &http_client, &fallback_playlist).await?;
+    let query_type = verify(query_type, ParrotError::Other(SOURCE_INVALID_QUERY))?;
+
+    let report =
+        normal_query_type_resolver(call, http, data, guild_id, &query_type, Mode::End, None)
+            .await?;
+
+    if !report.failed.is_empty() {
+        println!(
+            "[WARN] {} fallback tracks failed to load for guild {}",
+            report.failed.len(),
+            guild_id
+        );
+    }
+
+    Ok(())
 }
 
 pub async fn get_urls_from_playlist(
@@ -573,6 +1014,17 @@ pub async fn get_urls_from_playlist(
     url: String,
     mode: Option<Mode>,
 ) -> Result<Vec<Option<String>>, ParrotError> {
+    let entries = get_playlist_entries(http_client, url, mode).await?;
+    Ok(entries.into_iter().map(|v| v.source_url).collect())
+}
+
+/// Lists a playlist's tracks without loading any of them, which leaves each one
+/// with little more than its link and title.
+pub async fn get_playlist_entries(
+    http_client: &reqwest::Client,
+    url: String,
+    mode: Option<Mode>,
+) -> Result<Vec<AuxMetadata>, ParrotError> {
     let ytdl = YoutubeDl::new(http_client.clone(), url);
     let mut args = vec!["--flat-playlist", "-j"];
 
@@ -590,7 +1042,6 @@ pub async fn get_urls_from_playlist(
         .search(None)
         .await
         .map_err(|_| ParrotError::Other("Failed to fetch playlist"))?
-        .map(|v| v.source_url.clone())
         .collect();
 
     Ok(result)
@@ -1,28 +1,51 @@
 use crate::{
-    errors::ParrotError,
-    guild::cache::GuildCacheMap,
-    handlers::track_end::ModifyQueueHandler,
+    commands::{
+        queue_cleanup::{
+            dedupe, remove_tracks, remove_unavailable, remove_user, sync_stored_queue,
+        },
+        quiz::{check_quiz_command, is_quiz_running},
+        skip::force_skip_top_track,
+        undo::save_undo_snapshot,
+    },
+    errors::{ParrotError, verify},
+    guild::{
+        cache::GuildCacheMap,
+        metadata_store::MetadataStore,
+        settings::{GuildSettingsMap, LoopMode},
+    },
+    handlers::track_end::{ModifyQueueHandler, update_queue_messages},
     messaging::messages::{
-        QUEUE_EXPIRED, QUEUE_NO_SONGS, QUEUE_NOTHING_IS_PLAYING, QUEUE_NOW_PLAYING, QUEUE_PAGE,
-        QUEUE_PAGE_OF, QUEUE_UP_NEXT,
+        QUEUE_ACTION_MOVED_TOP, QUEUE_ACTION_NEXT, QUEUE_ACTION_PLAYING_NEXT, QUEUE_ACTION_REMOVE,
+        QUEUE_ACTION_TOP, QUEUE_DETAILS_CHANNEL, QUEUE_DETAILS_DURATION, QUEUE_DETAILS_POSITION,
+        QUEUE_DETAILS_REQUESTER, QUEUE_EXPIRED, QUEUE_FIND_JUMP, QUEUE_FIND_NONE,
+        QUEUE_FIND_PLAYING, QUEUE_FIND_REMOVE, QUEUE_FIND_RESULTS, QUEUE_NO_SONGS,
+        QUEUE_NOTHING_IS_PLAYING, QUEUE_NOW_PLAYING, QUEUE_PAGE, QUEUE_PAGE_OF,
+        QUEUE_SELECT_PLACEHOLDER, QUEUE_STATUS_AUTOPAUSE, QUEUE_STATUS_QUEUE_LOOP,
+        QUEUE_STATUS_TRACK_LOOP, QUEUE_SUMMARY_REMAINING, QUEUE_SUMMARY_TRACK,
+        QUEUE_SUMMARY_TRACKS, QUEUE_TRACK_GONE, QUEUE_TRACK_PLAYING, QUEUE_UP_NEXT, REMOVED_QUEUE,
+    },
+    permissions::check_component_permissions,
+    utils::{
+        create_embed_response, get_human_readable_timestamp, get_track_metadata, truncate_text,
     },
-    utils::{get_human_readable_timestamp, get_track_metadata},
 };
 use serenity::{
     all::{
-        ButtonStyle, CommandInteraction, CreateActionRow, CreateEmbedFooter,
-        CreateInteractionResponse, CreateInteractionResponseMessage, EditMessage,
+        ButtonStyle, CommandDataOptionValue, CommandInteraction, ComponentInteraction,
+        ComponentInteractionDataKind, CreateActionRow, CreateEmbedFooter,
+        CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
+        CreateSelectMenuKind, CreateSelectMenuOption, EditMessage,
     },
     builder::{CreateButton, CreateEmbed},
     client::Context,
     futures::StreamExt,
     model::{channel::Message, id::GuildId},
-    prelude::{RwLock, TypeMap},
+    prelude::{Mentionable, RwLock, TypeMap},
 };
 use songbird::{Event, TrackEvent, tracks::TrackHandle};
 use std::{
     cmp::{max, min},
-    fmt::Write,
+    fmt::{Display, Write},
     ops::Add,
     sync::Arc,
     time::Duration,
@@ -30,8 +53,41 @@ use std::{
 
 const EMBED_PAGE_SIZE: usize = 6;
 const EMBED_TIMEOUT: u64 = 3600;
+const SELECT_LABEL_LIMIT: usize = 100;
+const QUEUE_SELECT_ID: &str = "queue_select";
+const FIND_RESULTS_LIMIT: usize = 5;
+
+/// Every track action button's id starts with this, followed by the action and the track's uuid.
+pub const QUEUE_ACTION_PREFIX: &str = "queue_action_";
 
 pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let subcommand = interaction.data.options.first().cloned();
+
+    let Some(subcommand) = subcommand else {
+        return show(ctx, interaction).await;
+    };
+
+    let CommandDataOptionValue::SubCommand(args) = subcommand.value else {
+        unreachable!()
+    };
+
+    match subcommand.name.as_str() {
+        "find" => {
+            let query = args
+                .first()
+                .and_then(|arg| arg.value.as_str())
+                .unwrap_or_default();
+
+            find(ctx, interaction, query).await
+        }
+        "dedupe" => dedupe(ctx, interaction).await,
+        "removeuser" => remove_user(ctx, interaction, &args).await,
+        "removeunavailable" => remove_unavailable(ctx, interaction).await,
+        _ => show(ctx, interaction).await,
+    }
+}
+
+async fn show(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
     let guild_id = interaction.guild_id.unwrap();
     let manager = songbird::get(ctx).await.unwrap();
     let call = manager.get(guild_id).unwrap();
@@ -43,9 +99,10 @@ pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
     let num_pages = calculate_num_pages(&tracks);
     let message = build_nav_btns(
         CreateInteractionResponseMessage::new()
-            .add_embed(create_queue_embed(&tracks, 0, &ctx.data).await?),
+            .add_embed(create_queue_embed(&tracks, 0, guild_id, &ctx.data).await?),
         0,
         num_pages,
+        build_track_select(&tracks, 0, &ctx.data).await,
     );
 
     interaction
@@ -84,11 +141,24 @@ pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
     while let Some(mci) = cib.next().await {
         let btn_id = &mci.data.custom_id;
 
+        // paging through the queue would give a quiz's answers away
+        if is_quiz_running(&ctx.data, guild_id).await {
+            mci.create_response(&ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+            continue;
+        }
+
         // refetch the queue in case it changed
         let handler = call.lock().await;
         let tracks = handler.queue().current_queue();
         drop(handler);
 
+        if btn_id.as_str() == QUEUE_SELECT_ID {
+            let response = show_track_details(ctx, &mci, &tracks).await;
+            mci.create_response(&ctx, response).await?;
+            continue;
+        }
+
         let num_pages = calculate_num_pages(&tracks);
         let mut page_wlock = page.write().await;
 
@@ -102,9 +172,10 @@ pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
 
         let message = build_nav_btns(
             CreateInteractionResponseMessage::new()
-                .add_embed(create_queue_embed(&tracks, *page_wlock, &ctx.data).await?),
+                .add_embed(create_queue_embed(&tracks, *page_wlock, guild_id, &ctx.data).await?),
             *page_wlock,
             num_pages,
+            build_track_select(&tracks, *page_wlock, &ctx.data).await,
         );
 
         mci.create_response(&ctx, CreateInteractionResponse::UpdateMessage(message))
@@ -126,16 +197,305 @@ pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
     Ok(())
 }
 
+/// A queued track that matches a search.
+struct QueueMatch {
+    position: usize,
+    uuid: String,
+    title: String,
+    url: String,
+    score: usize,
+}
+
+/// How closely `text` matches a search, lower being closer, or [`None`] if it doesn't at all.
+/// The whole phrase beats its words showing up anywhere, which beats its letters merely
+/// showing up in order, e.g. `nvr gna` for `never gonna`.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let normalize = |text: &str| {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let (query, text) = (normalize(query), normalize(text));
+    if query.is_empty() {
+        return None;
+    }
+
+    if text.contains(&query) {
+        return Some(0);
+    }
+
+    if query.split(' ').all(|word| text.contains(word)) {
+        return Some(1);
+    }
+
+    let needle: Vec<char> = query.chars().filter(|c| *c != ' ').collect();
+    let haystack: Vec<char> = text.chars().collect();
+
+    // find the tightest stretch of text holding the letters in order,
+    // letters scattered across the whole title hardly count as a match
+    let span = (0..haystack.len())
+        .filter(|start| haystack[*start] == needle[0])
+        .filter_map(|start| {
+            let mut matched = 0;
+
+            for (idx, c) in haystack.iter().enumerate().skip(start) {
+                if *c == needle[matched] {
+                    matched += 1;
+                }
+
+                if matched == needle.len() {
+                    return Some(idx - start + 1);
+                }
+            }
+
+            None
+        })
+        .min()?;
+
+    (span <= needle.len() * 2).then_some(2 + span - needle.len())
+}
+
+/// Looks for tracks in the queue whose title or channel match the query,
+/// offering to jump to or remove each one that's up next.
+async fn find(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    query: &str,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let tracks = call.lock().await.queue().current_queue();
+
+    let mut matches: Vec<QueueMatch> = {
+        let data = ctx.data.read().await;
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+
+        tracks
+            .iter()
+            .enumerate()
+            .filter_map(|(position, track)| {
+                let uuid = track.uuid().to_string();
+                let metadata = metadata_store.retrieve_metadata(&uuid)?;
+
+                let score = [&metadata.title, &metadata.channel]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|text| fuzzy_score(query, text))
+                    .min()?;
+
+                Some(QueueMatch {
+                    position,
+                    uuid,
+                    title: metadata.title.clone().unwrap_or_default(),
+                    url: metadata.source_url.clone().unwrap_or_default(),
+                    score,
+                })
+            })
+            .collect()
+    };
+
+    matches.sort_by_key(|found| (found.score, found.position));
+    matches.truncate(FIND_RESULTS_LIMIT);
+
+    if matches.is_empty() {
+        let embed = CreateEmbed::new().description(format!("{} `{}`", QUEUE_FIND_NONE, query));
+        return create_embed_response(&ctx.http, interaction, embed).await;
+    }
+
+    let mut description = String::new();
+    let mut components = Vec::new();
+
+    for found in matches.iter() {
+        let position = match found.position {
+            0 => QUEUE_FIND_PLAYING.to_string(),
+            position => format!("{}.", position),
+        };
+
+        let _ = writeln!(
+            description,
+            "`{}` [{}]({})",
+            position, found.title, found.url
+        );
+
+        // what's playing can't be jumped to or removed
+        if found.position > 0 {
+            components.push(build_find_action_btns(&found.uuid, found.position));
+        }
+    }
+
+    let embed = CreateEmbed::new()
+        .title(QUEUE_FIND_RESULTS)
+        .description(description);
+
+    let message = CreateInteractionResponseMessage::new()
+        .add_embed(embed)
+        .components(components);
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+        .await
+        .map_err(Into::into)
+}
+
+fn build_find_action_btns(uuid: &str, position: usize) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}top:{}", QUEUE_ACTION_PREFIX, uuid))
+            .label(format!("{} {}", QUEUE_FIND_JUMP, position))
+            .style(ButtonStyle::Primary),
+        CreateButton::new(format!("{}remove:{}", QUEUE_ACTION_PREFIX, uuid))
+            .label(format!("{} {}", QUEUE_FIND_REMOVE, position))
+            .style(ButtonStyle::Danger),
+    ])
+}
+
+/// What a queue adds up to, so listeners know what they're in for.
+#[derive(Debug, PartialEq)]
+pub struct QueueSummary {
+    pub tracks: usize,
+    pub remaining: Duration,
+    pub has_unknown: bool,
+}
+
+/// Adds up the time left in the queue, which starts with the current track at `position`.
+/// Livestreams and tracks of unknown length can't be added up, so they're only flagged.
+pub fn summarize_queue(durations: &[Option<Duration>], position: Duration) -> QueueSummary {
+    let mut remaining = Duration::ZERO;
+    let mut has_unknown = false;
+
+    for (idx, duration) in durations.iter().enumerate() {
+        match duration {
+            Some(duration) if idx == 0 => remaining += duration.saturating_sub(position),
+            Some(duration) => remaining += *duration,
+            None => has_unknown = true,
+        }
+    }
+
+    QueueSummary {
+        tracks: durations.len(),
+        remaining,
+        has_unknown,
+    }
+}
+
+impl Display for QueueSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tracks = match self.tracks {
+            1 => format!("1 {}", QUEUE_SUMMARY_TRACK),
+            tracks => format!("{} {}", tracks, QUEUE_SUMMARY_TRACKS),
+        };
+
+        // at least this much, as some of it can't be measured
+        let plus = if self.has_unknown { "+" } else { "" };
+
+        f.write_str(&format!(
+            "🎶 **{}** • ⏳ `{}{}` {}",
+            tracks,
+            get_human_readable_timestamp(Some(self.remaining)),
+            plus,
+            QUEUE_SUMMARY_REMAINING
+        ))
+    }
+}
+
+/// The playback modes shown at the bottom of the queue.
+pub struct QueueStatus {
+    pub loop_mode: LoopMode,
+    pub autopause: bool,
+    pub volume: f32,
+}
+
+impl Display for QueueStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut indicators = Vec::new();
+
+        match self.loop_mode {
+            LoopMode::Off => {}
+            LoopMode::Track => indicators.push(QUEUE_STATUS_TRACK_LOOP.to_string()),
+            LoopMode::Queue => indicators.push(QUEUE_STATUS_QUEUE_LOOP.to_string()),
+        }
+
+        if self.autopause {
+            indicators.push(QUEUE_STATUS_AUTOPAUSE.to_string());
+        }
+
+        indicators.push(format!("🔊 {:.0}%", self.volume * 100.0));
+        f.write_str(&indicators.join(" • "))
+    }
+}
+
+async fn get_queue_status(
+    tracks: &[TrackHandle],
+    guild_id: GuildId,
+    data: &Arc<RwLock<TypeMap>>,
+) -> QueueStatus {
+    let (loop_mode, autopause) = {
+        let data = data.read().await;
+        data.get::<GuildSettingsMap>()
+            .unwrap()
+            .get(&guild_id)
+            .map(|guild_settings| (guild_settings.loop_mode, guild_settings.autopause))
+            .unwrap_or_default()
+    };
+
+    let volume = match tracks.first() {
+        Some(track) => track.get_info().await.ok().map(|state| state.volume),
+        None => None,
+    };
+
+    QueueStatus {
+        loop_mode,
+        autopause,
+        volume: volume.unwrap_or(1.0),
+    }
+}
+
+async fn get_queue_summary(tracks: &[TrackHandle], data: &Arc<RwLock<TypeMap>>) -> QueueSummary {
+    let position = match tracks.first() {
+        Some(track) => track
+            .get_info()
+            .await
+            .map(|state| state.position)
+            .unwrap_or_default(),
+        None => Duration::ZERO,
+    };
+
+    let data = data.read().await;
+    let metadata_store = data.get::<MetadataStore>().unwrap();
+
+    let durations: Vec<Option<Duration>> = tracks
+        .iter()
+        .map(|track| {
+            metadata_store
+                .retrieve_metadata(&track.uuid().to_string())
+                .and_then(|metadata| metadata.duration)
+        })
+        .collect();
+
+    summarize_queue(&durations, position)
+}
+
 pub async fn create_queue_embed(
     tracks: &[TrackHandle],
     page: usize,
+    guild_id: GuildId,
     data: &Arc<RwLock<TypeMap>>,
 ) -> Result<CreateEmbed, ParrotError> {
     let mut embed = CreateEmbed::new();
 
+    if !tracks.is_empty() {
+        embed = embed.description(format!("{}", get_queue_summary(tracks, data).await));
+    }
+
     let description = if !tracks.is_empty() {
         let metadata = get_track_metadata(&tracks[0], data).await?;
-        embed = embed.thumbnail(metadata.thumbnail.unwrap());
+        if let Some(thumbnail) = &metadata.thumbnail {
+            embed = embed.thumbnail(thumbnail);
+        }
 
         format!(
             "[{}]({}) • `{}`",
@@ -155,11 +515,12 @@ pub async fn create_queue_embed(
             false,
         )
         .footer(CreateEmbedFooter::new(format!(
-            "{} {} {} {}",
+            "{} {} {} {} • {}",
             QUEUE_PAGE,
             page + 1,
             QUEUE_PAGE_OF,
             calculate_num_pages(tracks),
+            get_queue_status(tracks, guild_id, data).await,
         )));
 
     Ok(embed)
@@ -177,19 +538,266 @@ pub fn build_nav_btns(
     message: CreateInteractionResponseMessage,
     page: usize,
     num_pages: usize,
+    track_select: Option<CreateActionRow>,
 ) -> CreateInteractionResponseMessage {
     let (cant_left, cant_right) = (page < 1, page >= num_pages - 1);
 
-    let components = vec![CreateActionRow::Buttons(vec![
+    let mut components = vec![CreateActionRow::Buttons(vec![
         build_single_nav_btn("<<", cant_left),
         build_single_nav_btn("<", cant_left),
         build_single_nav_btn(">", cant_right),
         build_single_nav_btn(">>", cant_right),
     ])];
+    components.extend(track_select);
 
     message.components(components)
 }
 
+/// Lists the tracks on a page of the queue, so one can be picked to act on.
+/// Returns [`None`] if there's nothing up next on that page.
+pub async fn build_track_select(
+    tracks: &[TrackHandle],
+    page: usize,
+    data: &Arc<RwLock<TypeMap>>,
+) -> Option<CreateActionRow> {
+    let start_idx = EMBED_PAGE_SIZE * page;
+    let mut options = Vec::new();
+
+    for (idx, track) in tracks
+        .iter()
+        .enumerate()
+        .skip(start_idx + 1)
+        .take(EMBED_PAGE_SIZE)
+    {
+        let title = get_track_metadata(track, data)
+            .await
+            .ok()
+            .and_then(|metadata| metadata.title)
+            .unwrap_or_else(|| "Unable to retrieve title".to_string());
+
+        let label = truncate_text(&format!("{}. {}", idx, title), SELECT_LABEL_LIMIT);
+        options.push(CreateSelectMenuOption::new(label, track.uuid().to_string()));
+    }
+
+    if options.is_empty() {
+        return None;
+    }
+
+    let select = CreateSelectMenu::new(QUEUE_SELECT_ID, CreateSelectMenuKind::String { options })
+        .placeholder(QUEUE_SELECT_PLACEHOLDER);
+
+    Some(CreateActionRow::SelectMenu(select))
+}
+
+/// The command a track action stands in for, which decides who may use it.
+pub fn get_queue_action_command(action: &str) -> Option<&'static str> {
+    match action {
+        "next" => Some("superplay"),
+        "top" => Some("skip"),
+        "remove" => Some("remove"),
+        _ => None,
+    }
+}
+
+fn build_track_action_btns(uuid: &str) -> CreateActionRow {
+    let build_btn = |action: &str, label: &str, style: ButtonStyle| {
+        CreateButton::new(format!("{}{}:{}", QUEUE_ACTION_PREFIX, action, uuid))
+            .label(label)
+            .style(style)
+    };
+
+    CreateActionRow::Buttons(vec![
+        build_btn("next", QUEUE_ACTION_NEXT, ButtonStyle::Primary),
+        build_btn("top", QUEUE_ACTION_TOP, ButtonStyle::Primary),
+        build_btn("remove", QUEUE_ACTION_REMOVE, ButtonStyle::Danger),
+    ])
+}
+
+/// Shows whoever picked a track everything about it, along with what they can do with it.
+async fn show_track_details(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    tracks: &[TrackHandle],
+) -> CreateInteractionResponse {
+    let uuid = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values.first(),
+        _ => None,
+    };
+
+    let position = uuid.and_then(|uuid| {
+        tracks
+            .iter()
+            .position(|track| track.uuid().to_string() == *uuid)
+    });
+
+    let message = match (uuid, position) {
+        (Some(uuid), Some(position)) => {
+            match create_track_details_embed(&tracks[position], position, &ctx.data).await {
+                Ok(embed) => CreateInteractionResponseMessage::new()
+                    .add_embed(embed)
+                    .components(vec![build_track_action_btns(uuid)]),
+                Err(err) => CreateInteractionResponseMessage::new()
+                    .add_embed(CreateEmbed::new().description(format!("{err}"))),
+            }
+        }
+        _ => CreateInteractionResponseMessage::new()
+            .add_embed(CreateEmbed::new().description(QUEUE_TRACK_GONE)),
+    };
+
+    CreateInteractionResponse::Message(message.ephemeral(true))
+}
+
+async fn create_track_details_embed(
+    track: &TrackHandle,
+    position: usize,
+    data: &Arc<RwLock<TypeMap>>,
+) -> Result<CreateEmbed, ParrotError> {
+    let metadata = get_track_metadata(track, data).await?;
+
+    let requester = {
+        let data = data.read().await;
+        data.get::<MetadataStore>()
+            .unwrap()
+            .retrieve_requester(&track.uuid().to_string())
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(
+            metadata
+                .title
+                .unwrap_or("Track doesn't have title".to_owned()),
+        )
+        .field(QUEUE_DETAILS_POSITION, format!(">>> {}", position), true)
+        .field(
+            QUEUE_DETAILS_DURATION,
+            format!(">>> {}", get_human_readable_timestamp(metadata.duration)),
+            true,
+        )
+        .field(
+            QUEUE_DETAILS_CHANNEL,
+            format!(">>> {}", metadata.channel.as_deref().unwrap_or("N/A")),
+            true,
+        );
+
+    if let Some(source_url) = metadata.source_url {
+        embed = embed.url(source_url);
+    }
+
+    if let Some(requester) = requester {
+        embed = embed.field(
+            QUEUE_DETAILS_REQUESTER,
+            format!(">>> {}", requester.mention()),
+            true,
+        );
+    }
+
+    if let Some(thumbnail) = metadata.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    Ok(embed)
+}
+
+/// Carries out a track action picked from a queue view, refreshing every view afterwards.
+pub async fn queue_action(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let Some((action, uuid)) = interaction
+        .data
+        .custom_id
+        .strip_prefix(QUEUE_ACTION_PREFIX)
+        .and_then(|custom_id| custom_id.split_once(':'))
+    else {
+        return Ok(());
+    };
+
+    let Some(command_name) = get_queue_action_command(action) else {
+        return Ok(());
+    };
+
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).ok_or(ParrotError::NotConnected)?;
+
+    let find_position = |tracks: &[TrackHandle]| {
+        tracks
+            .iter()
+            .position(|track| track.uuid().to_string() == uuid)
+            .ok_or(ParrotError::Other(QUEUE_TRACK_GONE))
+    };
+
+    let tracks = call.lock().await.queue().current_queue();
+    let position = find_position(&tracks)?;
+    verify(position > 0, ParrotError::Other(QUEUE_TRACK_PLAYING))?;
+
+    // requesters may remove their own tracks, just like with /remove
+    let owned = (action == "remove").then_some((position, position + 1));
+    check_component_permissions(ctx, interaction, command_name, owned).await?;
+    check_quiz_command(&ctx.data, guild_id, command_name).await?;
+
+    let handler = call.lock().await;
+
+    // the queue might have moved on while checking
+    let tracks = handler.queue().current_queue();
+    let position = find_position(&tracks)?;
+    verify(position > 0, ParrotError::Other(QUEUE_TRACK_PLAYING))?;
+
+    // looked up first, as removing the track forgets its metadata
+    let metadata = get_track_metadata(&tracks[position], &ctx.data).await?;
+
+    if action == "remove" {
+        let removed = position..position + 1;
+        save_undo_snapshot(&ctx.data, guild_id, "remove", &tracks, removed, true).await;
+        remove_tracks(ctx, guild_id, &handler, &tracks, &[position]).await;
+    } else {
+        handler.queue().modify_queue(|queue| {
+            if let Some(queued) = queue.remove(position) {
+                queue.insert(1, queued);
+            }
+        });
+
+        // the top of the queue is what's playing, so the track takes over right away
+        if action == "top" {
+            force_skip_top_track(&handler).await?;
+        }
+
+        let tracks = handler.queue().current_queue();
+        sync_stored_queue(&ctx.data, guild_id, &tracks).await;
+    }
+
+    // refetch the queue after modification
+    let tracks = handler.queue().current_queue();
+    drop(handler);
+    let outcome = match action {
+        "next" => QUEUE_ACTION_PLAYING_NEXT,
+        "top" => QUEUE_ACTION_MOVED_TOP,
+        _ => REMOVED_QUEUE,
+    };
+
+    let embed = CreateEmbed::new().description(format!(
+        "{} [**{}**]({})",
+        outcome,
+        metadata.title.unwrap_or_default(),
+        metadata.source_url.unwrap_or_default()
+    ));
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &tracks, guild_id).await;
+    Ok(())
+}
+
 async fn build_queue_page(
     tracks: &[TrackHandle],
     page: usize,
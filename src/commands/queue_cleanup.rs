@@ -0,0 +1,276 @@
+use crate::{
+    errors::{ParrotError, verify},
+    guild::{metadata_store::MetadataStore, stored_queue::GuildStoredQueueMap},
+    handlers::track_end::update_queue_messages,
+    messaging::message::ParrotMessage,
+    permissions::check_member_permissions,
+    utils::create_response,
+};
+use serenity::{
+    all::{CommandDataOption, CommandInteraction},
+    client::Context,
+    model::id::GuildId,
+    prelude::{Mentionable, RwLock, TypeMap},
+};
+use songbird::{
+    Call,
+    tracks::{PlayMode, TrackHandle},
+};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::MutexGuard;
+
+/// Mass removals are held to the same level as `/clear`.
+const CLEANUP_COMMAND: &str = "clear";
+
+/// Removes every track whose link already showed up earlier in the queue.
+pub async fn dedupe(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let member = interaction.member.as_deref();
+    check_member_permissions(ctx, guild_id, CLEANUP_COMMAND, member).await?;
+
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+    let handler = call.lock().await;
+
+    let tracks = handler.queue().current_queue();
+    verify(tracks.len() > 1, ParrotError::QueueEmpty)?;
+
+    let positions = {
+        let data = ctx.data.read().await;
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+
+        let source_urls: Vec<Option<&str>> = tracks
+            .iter()
+            .map(|track| {
+                metadata_store
+                    .retrieve_metadata(&track.uuid().to_string())
+                    .and_then(|metadata| metadata.source_url.as_deref())
+            })
+            .collect();
+
+        find_duplicates(&source_urls)
+    };
+
+    let count = remove_tracks(ctx, guild_id, &handler, &tracks, &positions).await;
+    let queue = handler.queue().current_queue();
+    drop(handler);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::RemoveDuplicates { count },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+/// Removes everything a member queued that isn't playing yet.
+/// Members may always clear out their own tracks, whatever the command's level.
+pub async fn remove_user(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let user_id = args
+        .first()
+        .and_then(|arg| arg.value.as_user_id())
+        .unwrap_or(interaction.user.id);
+
+    if user_id != interaction.user.id {
+        let member = interaction.member.as_deref();
+        check_member_permissions(ctx, guild_id, CLEANUP_COMMAND, member).await?;
+    }
+
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+    let handler = call.lock().await;
+
+    let tracks = handler.queue().current_queue();
+    verify(tracks.len() > 1, ParrotError::QueueEmpty)?;
+
+    let positions: Vec<usize> = {
+        let data = ctx.data.read().await;
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+
+        tracks
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, track)| {
+                metadata_store.retrieve_requester(&track.uuid().to_string()) == Some(user_id)
+            })
+            .map(|(position, _)| position)
+            .collect()
+    };
+
+    let count = remove_tracks(ctx, guild_id, &handler, &tracks, &positions).await;
+    let queue = handler.queue().current_queue();
+    drop(handler);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::RemoveRequester {
+            mention: user_id.mention(),
+            count,
+        },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+/// Removes tracks that won't play, either because their metadata couldn't be loaded
+/// or because they already failed.
+pub async fn remove_unavailable(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let member = interaction.member.as_deref();
+    check_member_permissions(ctx, guild_id, CLEANUP_COMMAND, member).await?;
+
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+    let handler = call.lock().await;
+
+    let tracks = handler.queue().current_queue();
+    verify(tracks.len() > 1, ParrotError::QueueEmpty)?;
+
+    let mut errored = HashSet::new();
+    for (position, track) in tracks.iter().enumerate().skip(1) {
+        let failed = match track.get_info().await {
+            Ok(state) => matches!(state.playing, PlayMode::Errored(_)),
+            Err(_) => true,
+        };
+
+        if failed {
+            errored.insert(position);
+        }
+    }
+
+    let positions: Vec<usize> = {
+        let data = ctx.data.read().await;
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+
+        (1..tracks.len())
+            .filter(|position| {
+                let metadata =
+                    metadata_store.retrieve_metadata(&tracks[*position].uuid().to_string());
+
+                errored.contains(position)
+                    || metadata.is_none_or(|metadata| {
+                        metadata.title.is_none() || metadata.source_url.is_none()
+                    })
+            })
+            .collect()
+    };
+
+    let count = remove_tracks(ctx, guild_id, &handler, &tracks, &positions).await;
+    let queue = handler.queue().current_queue();
+    drop(handler);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::RemoveUnavailable { count },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+/// The positions of tracks whose link already showed up earlier in the queue.
+/// Tracks without a link can't be compared, so they're never considered repeats.
+pub fn find_duplicates(source_urls: &[Option<&str>]) -> Vec<usize> {
+    let mut seen = HashSet::new();
+
+    source_urls
+        .iter()
+        .enumerate()
+        .filter_map(|(position, source_url)| {
+            let source_url = (*source_url)?;
+            (!seen.insert(source_url)).then_some(position)
+        })
+        .collect()
+}
+
+/// Takes the tracks at the given positions out of the queue along with their metadata,
+/// and brings the stored queue in line with what's left. Returns how many tracks were removed.
+pub async fn remove_tracks(
+    ctx: &Context,
+    guild_id: GuildId,
+    handler: &MutexGuard<'_, Call>,
+    tracks: &[TrackHandle],
+    positions: &[usize],
+) -> usize {
+    // whatever's playing is left to /skip
+    let removed: Vec<&TrackHandle> = positions
+        .iter()
+        .filter(|position| **position > 0)
+        .filter_map(|position| tracks.get(*position))
+        .collect();
+
+    if removed.is_empty() {
+        return 0;
+    }
+
+    let uuids: HashSet<_> = removed.iter().map(|track| track.uuid()).collect();
+    handler.queue().modify_queue(|queue| {
+        queue.retain(|track| !uuids.contains(&track.uuid()));
+    });
+
+    let mut data = ctx.data.write().await;
+    let metadata_store = data.get_mut::<MetadataStore>().unwrap();
+
+    for track in removed.iter() {
+        metadata_store.remove_metadata(&track.uuid().to_string());
+    }
+    drop(data);
+
+    let remaining = handler.queue().current_queue();
+    sync_stored_queue(&ctx.data, guild_id, &remaining).await;
+
+    removed.len()
+}
+
+/// Brings the stored queue in line with the tracks that are actually queued,
+/// after they were removed or moved around.
+pub async fn sync_stored_queue(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    tracks: &[TrackHandle],
+) {
+    let mut data = data.write().await;
+    let metadata_store = data.get::<MetadataStore>().unwrap();
+
+    let source_urls: Vec<Option<String>> = tracks
+        .iter()
+        .map(|track| {
+            metadata_store
+                .retrieve_metadata(&track.uuid().to_string())
+                .and_then(|metadata| metadata.source_url.clone())
+        })
+        .collect();
+
+    if let Some(guild_stored_queue) = data
+        .get_mut::<GuildStoredQueueMap>()
+        .unwrap()
+        .get_mut(&guild_id)
+    {
+        let source_urls: Vec<Option<&str>> = source_urls
+            .iter()
+            .map(|source_url| source_url.as_deref())
+            .collect();
+        guild_stored_queue.sync(&source_urls);
+    }
+}
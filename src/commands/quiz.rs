@@ -0,0 +1,719 @@
+use crate::{
+    commands::{
+        play::{
+            Mode, QueryType, get_playlist_entries, get_query_type, get_requery_shapes,
+            normal_query_type_resolver,
+        },
+        skip::force_skip_top_track,
+        summon::summon,
+    },
+    errors::{ParrotError, verify},
+    guild::{
+        cache::GuildCacheMap,
+        http_client::HttpClientInstance,
+        metadata_store::MetadataStore,
+        settings::{GuildSettings, GuildSettingsMap},
+        stored_queue::{GuildStoredQueue, GuildStoredQueueMap},
+    },
+    handlers::track_end::update_queue_messages,
+    messaging::{
+        message::ParrotMessage,
+        messages::{
+            FAIL_NO_QUIZ, FAIL_QUIZ_BUSY, FAIL_QUIZ_IN_PROGRESS, FAIL_QUIZ_METADATA,
+            FAIL_QUIZ_PLAYLIST, FAIL_QUIZ_RUNNING, QUIZ_ALREADY_ANSWERED, QUIZ_ANSWER,
+            QUIZ_CORRECT, QUIZ_FAILED, QUIZ_NO_SCORES, QUIZ_ROUND, QUIZ_ROUND_OF,
+            QUIZ_ROUND_PROMPT, QUIZ_ROUND_PROMPT_CHAT, QUIZ_SCOREBOARD, QUIZ_TIMES_UP, QUIZ_WRONG,
+        },
+    },
+    utils::{create_response, edit_response, truncate_text},
+};
+use lazy_static::lazy_static;
+use rand::seq::SliceRandom;
+use serenity::{
+    all::{
+        ButtonStyle, ChannelId, CommandDataOption, CommandDataOptionValue, CommandInteraction,
+        CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateMessage, EditMessage, Message,
+    },
+    client::Context,
+    futures::StreamExt,
+    model::id::{GuildId, UserId},
+    prelude::{Mentionable, Mutex, RwLock, TypeMap},
+};
+use songbird::{Call, tracks::TrackHandle};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fmt::Write,
+    sync::Arc,
+    time::Duration,
+};
+
+/// How long each clip plays for, which is also how long there is to guess it.
+/// It's kept short enough that clips never count as plays in the stats.
+const ROUND_LENGTH: Duration = Duration::from_secs(20);
+
+/// The pause between revealing one answer and starting the next clip.
+const ROUND_BREAK: Duration = Duration::from_secs(5);
+
+/// Clips start somewhere in the middle of a track, but not so far in that the
+/// intro of a long mix is all anyone gets to hear.
+const MAX_CLIP_START: Duration = Duration::from_secs(60);
+
+const DEFAULT_QUIZ_ROUNDS: usize = 10;
+pub const MAX_QUIZ_ROUNDS: usize = 20;
+
+/// Buttons per round, one of them being the right answer.
+pub const QUIZ_CHOICES: usize = 4;
+
+/// How close a guess has to be to an answer, from 0 to 1, to count as right.
+const MIN_ANSWER_SIMILARITY: f64 = 0.8;
+
+const QUIZ_CHOICE_PREFIX: &str = "quiz_";
+
+lazy_static! {
+    /// Reading guesses from chat needs the privileged message content intent,
+    /// which bots have to be granted before they're allowed to ask for it.
+    pub static ref QUIZ_CHAT_ANSWERS: bool =
+        env::var("QUIZ_CHAT_ANSWERS").is_ok_and(|value| value == "true");
+}
+
+pub async fn quiz(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let subcommand = interaction.data.options.first().unwrap().clone();
+
+    let CommandDataOptionValue::SubCommand(args) = subcommand.value else {
+        unreachable!()
+    };
+
+    match subcommand.name.as_str() {
+        "start" => start(ctx, interaction, &args).await,
+        "stop" => stop(ctx, interaction).await,
+        _ => unreachable!(),
+    }
+}
+
+async fn start(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let get_arg = |name: &str| {
+        args.iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| &arg.value)
+    };
+
+    let playlist = get_arg("playlist")
+        .and_then(|value| value.as_str())
+        .unwrap()
+        .to_string();
+    let rounds = get_arg("rounds")
+        .and_then(|value| value.as_i64())
+        .map(|rounds| (rounds.max(1) as usize).min(MAX_QUIZ_ROUNDS))
+        .unwrap_or(DEFAULT_QUIZ_ROUNDS);
+
+    verify(
+        !is_quiz_running(&ctx.data, guild_id).await,
+        ParrotError::Other(FAIL_QUIZ_RUNNING),
+    )?;
+
+    summon(ctx, interaction, false).await?;
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    // the quiz takes over the queue, so it shouldn't throw anyone's tracks away
+    let queue_is_empty = call.lock().await.queue().is_empty();
+    verify(queue_is_empty, ParrotError::Other(FAIL_QUIZ_BUSY))?;
+
+    let http_client = {
+        let data = ctx.data.read().await;
+        data.get::<HttpClientInstance>().unwrap().clone()
+    };
+
+    // listing a playlist takes longer than an interaction can wait for a reply
+    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
+
+    let (query_type, domain) = get_query_type(&ctx.data, &http_client, &playlist).await?;
+
+    if let Some(domain) = domain {
+        let is_allowed = {
+            let data = ctx.data.read().await;
+            let default_settings = GuildSettings::new(guild_id);
+            data.get::<GuildSettingsMap>()
+                .unwrap()
+                .get(&guild_id)
+                .unwrap_or(&default_settings)
+                .is_domain_allowed(&domain)
+        };
+
+        if !is_allowed {
+            return create_response(
+                &ctx.http,
+                interaction,
+                ParrotMessage::PlayDomainBanned { domain },
+            )
+            .await;
+        }
+    }
+
+    let Some(QueryType::PlaylistLink(url)) = query_type else {
+        return Err(ParrotError::Other(FAIL_QUIZ_PLAYLIST));
+    };
+
+    // shuffled so the rounds don't come in the order everyone knows them in
+    let entries: Vec<(String, String)> =
+        get_playlist_entries(&http_client, url, Some(Mode::Shuffle))
+            .await?
+            .into_iter()
+            .filter_map(|metadata| Some((metadata.source_url?, metadata.title?)))
+            .collect();
+
+    verify(
+        entries.len() >= QUIZ_CHOICES,
+        ParrotError::Other(FAIL_QUIZ_PLAYLIST),
+    )?;
+
+    let channel_id = interaction.channel_id;
+
+    {
+        let mut data = ctx.data.write().await;
+
+        // nothing the quiz queues should carry on once it's over
+        data.get_mut::<GuildStoredQueueMap>()
+            .unwrap()
+            .entry(guild_id)
+            .or_insert_with(GuildStoredQueue::new)
+            .continue_play = false;
+
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let cache = cache_map.entry(guild_id).or_default();
+
+        // now playing messages would give the answers away
+        cache.live_messages.clear();
+
+        // the lock is held until the game is in the cache, so anything it sets off knows about it
+        let game = run_quiz(ctx.clone(), guild_id, channel_id, call, entries, rounds);
+        cache.quiz = Some(tokio::spawn(game));
+    }
+
+    edit_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::QuizStarted { rounds },
+    )
+    .await?;
+    Ok(())
+}
+
+async fn stop(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let cancelled = cancel_quiz(&ctx.data, guild_id).await;
+    verify(cancelled, ParrotError::Other(FAIL_NO_QUIZ))?;
+
+    let manager = songbird::get(ctx).await.unwrap();
+    if let Some(call) = manager.get(guild_id) {
+        call.lock().await.queue().stop();
+    }
+
+    update_queue_messages(&ctx.http, &ctx.data, &[], guild_id).await;
+    create_response(&ctx.http, interaction, ParrotMessage::QuizStopped).await
+}
+
+/// Calls off the guild's quiz, if there's one going on. Returns whether there was.
+pub async fn cancel_quiz(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> bool {
+    let mut data = data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+
+    let game = cache_map
+        .get_mut(&guild_id)
+        .and_then(|cache| cache.quiz.take());
+
+    if let Some(game) = &game {
+        game.abort();
+    }
+
+    game.is_some()
+}
+
+pub async fn is_quiz_running(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> bool {
+    let data = data.read().await;
+    data.get::<GuildCacheMap>()
+        .unwrap()
+        .get(&guild_id)
+        .is_some_and(|cache| cache.quiz.is_some())
+}
+
+/// Commands that would give the answers away, pull the queue out from under a quiz
+/// or play clips outside of their rounds.
+pub fn is_blocked_during_quiz(command_name: &str) -> bool {
+    matches!(
+        command_name,
+        "autopause"
+            | "clear"
+            | "favorites"
+            | "like"
+            | "loop"
+            | "np"
+            | "pause"
+            | "play"
+            | "queue"
+            | "remove"
+            | "resume"
+            | "seek"
+            | "shuffle"
+            | "skip"
+            | "stop"
+            | "superplay"
+            | "undo"
+            | "voteskip"
+    )
+}
+
+/// Refuses commands that would spoil the guild's quiz, if there's one going on.
+pub async fn check_quiz_command(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    command_name: &str,
+) -> Result<(), ParrotError> {
+    if !is_blocked_during_quiz(command_name) {
+        return Ok(());
+    }
+
+    verify(
+        !is_quiz_running(data, guild_id).await,
+        ParrotError::Other(FAIL_QUIZ_IN_PROGRESS),
+    )
+}
+
+/// A round's clip, along with where it stands in the quiz.
+struct QuizRound {
+    track: TrackHandle,
+    /// The title as the playlist lists it, which is what the choices go by.
+    title: String,
+    number: usize,
+    total: usize,
+}
+
+async fn run_quiz(
+    ctx: Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    call: Arc<Mutex<Call>>,
+    entries: Vec<(String, String)>,
+    rounds: usize,
+) {
+    let titles: Vec<String> = entries.iter().map(|(_, title)| title.clone()).collect();
+    let total = rounds.min(entries.len());
+    let mut entries = entries.into_iter();
+
+    let mut scores: HashMap<UserId, u32> = HashMap::new();
+    let mut played = 0;
+
+    while played < total {
+        // clips are queued a round at a time, so one that ends early has nothing to hand over to
+        let Some((track, title)) = queue_next_clip(&ctx, guild_id, &call, &mut entries).await
+        else {
+            break;
+        };
+
+        played += 1;
+        let round = QuizRound {
+            track,
+            title,
+            number: played,
+            total,
+        };
+
+        if let Err(err) = play_round(&ctx, channel_id, &call, round, &titles, &mut scores).await {
+            println!(
+                "[ERROR] Failed to play quiz round {} in guild {} due to {}",
+                played, guild_id, err
+            );
+            break;
+        }
+    }
+
+    if played == 0 {
+        channel_id.say(&ctx.http, QUIZ_FAILED).await.ok();
+        return finish_quiz(&ctx, guild_id, &call).await;
+    }
+
+    let embed = create_scoreboard_embed(&rank_scores(&scores));
+    channel_id
+        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+        .await
+        .ok();
+
+    finish_quiz(&ctx, guild_id, &call).await;
+}
+
+/// Queues the next playlist entry that loads, held paused until its round starts.
+/// Returns its track along with its title as the playlist lists it.
+async fn queue_next_clip(
+    ctx: &Context,
+    guild_id: GuildId,
+    call: &Arc<Mutex<Call>>,
+    entries: &mut std::vec::IntoIter<(String, String)>,
+) -> Option<(TrackHandle, String)> {
+    for (source_url, title) in entries {
+        let query_type = QueryType::VideoLink(source_url);
+        let report = normal_query_type_resolver(
+            call,
+            &ctx.http,
+            &ctx.data,
+            guild_id,
+            &query_type,
+            Mode::End,
+            None,
+        )
+        .await;
+
+        // found at the back, in case anything else got queued in the meantime
+        let queued = call.lock().await.queue().current_queue().pop();
+
+        if report.is_ok_and(|report| report.failed.is_empty())
+            && let Some(track) = queued
+        {
+            // it starts as soon as it's queued, which would be a head start
+            track.pause().ok();
+            return Some((track, title));
+        }
+    }
+
+    None
+}
+
+async fn play_round(
+    ctx: &Context,
+    channel_id: ChannelId,
+    call: &Arc<Mutex<Call>>,
+    round: QuizRound,
+    titles: &[String],
+    scores: &mut HashMap<UserId, u32>,
+) -> Result<(), ParrotError> {
+    let QuizRound {
+        track,
+        title,
+        number,
+        total,
+    } = round;
+
+    let metadata = {
+        let data = ctx.data.read().await;
+        data.get::<MetadataStore>()
+            .unwrap()
+            .retrieve_metadata(&track.uuid().to_string())
+            .cloned()
+    };
+    let metadata = verify(metadata, ParrotError::Other(FAIL_QUIZ_METADATA))?;
+
+    // the title the track loaded with counts too, in case it's worded differently
+    let mut answers = get_quiz_answers(&title, metadata.artist.as_deref());
+    for answer in get_quiz_answers(metadata.title.as_deref().unwrap_or_default(), None) {
+        if !answers.contains(&answer) {
+            answers.push(answer);
+        }
+    }
+
+    let (choices, correct) = pick_choices(&title, titles, QUIZ_CHOICES);
+
+    let _ = track.seek(get_clip_start(metadata.duration));
+
+    let mut message = channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new()
+                .embed(create_round_embed(number, total))
+                .components(vec![build_choice_btns(&choices)]),
+        )
+        .await?;
+
+    track.play().ok();
+
+    let winner = collect_answer(ctx, channel_id, &message, &answers, correct).await;
+
+    // a clip shorter than a round has already ended, and taken itself out of the queue
+    let handler = call.lock().await;
+    let is_current = handler
+        .queue()
+        .current()
+        .is_some_and(|current| current.uuid() == track.uuid());
+
+    if is_current {
+        force_skip_top_track(&handler).await?;
+    }
+    drop(handler);
+
+    if let Some(winner) = winner {
+        *scores.entry(winner).or_default() += 1;
+    }
+
+    let mut reveal = format!(
+        "{} [**{}**]({})",
+        QUIZ_ANSWER,
+        title,
+        metadata.source_url.unwrap_or_default()
+    );
+    reveal = match winner {
+        Some(winner) => format!("✅ {} {}\n{}", winner.mention(), QUIZ_CORRECT, reveal),
+        None => format!("{}\n{}", QUIZ_TIMES_UP, reveal),
+    };
+
+    message
+        .edit(
+            &ctx.http,
+            EditMessage::new()
+                .embed(create_round_embed(number, total).description(reveal))
+                .components(vec![]),
+        )
+        .await?;
+
+    tokio::time::sleep(ROUND_BREAK).await;
+    Ok(())
+}
+
+/// Waits for someone to get the round right, either by pressing the right button or,
+/// when it's enabled, by typing the answer in chat. Everyone gets one button press a round.
+async fn collect_answer(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message: &Message,
+    answers: &[String],
+    correct: usize,
+) -> Option<UserId> {
+    let mut presses = message
+        .await_component_interactions(ctx)
+        .timeout(ROUND_LENGTH)
+        .stream();
+    let mut replies = channel_id.await_replies(ctx).timeout(ROUND_LENGTH).stream();
+
+    let mut answered: HashSet<UserId> = HashSet::new();
+    let deadline = tokio::time::sleep(ROUND_LENGTH);
+    tokio::pin!(deadline);
+
+    loop {
+        tokio::select! {
+            _ = &mut deadline => return None,
+            Some(press) = presses.next() => {
+                let choice = press
+                    .data
+                    .custom_id
+                    .strip_prefix(QUIZ_CHOICE_PREFIX)
+                    .and_then(|idx| idx.parse::<usize>().ok());
+
+                let is_first_try = answered.insert(press.user.id);
+
+                if is_first_try && choice == Some(correct) {
+                    press
+                        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+                        .await
+                        .ok();
+                    return Some(press.user.id);
+                }
+
+                let feedback = match is_first_try {
+                    true => QUIZ_WRONG,
+                    false => QUIZ_ALREADY_ANSWERED,
+                };
+
+                let response = CreateInteractionResponseMessage::new()
+                    .embed(CreateEmbed::default().description(feedback))
+                    .ephemeral(true);
+
+                press
+                    .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                    .await
+                    .ok();
+            }
+            Some(reply) = replies.next(), if *QUIZ_CHAT_ANSWERS => {
+                if !reply.author.bot && is_correct_answer(&reply.content, answers) {
+                    reply.react(&ctx.http, '✅').await.ok();
+                    return Some(reply.author.id);
+                }
+            }
+        }
+    }
+}
+
+async fn finish_quiz(ctx: &Context, guild_id: GuildId, call: &Arc<Mutex<Call>>) {
+    {
+        let mut data = ctx.data.write().await;
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        if let Some(cache) = cache_map.get_mut(&guild_id) {
+            cache.quiz = None;
+        }
+    }
+
+    call.lock().await.queue().stop();
+    update_queue_messages(&ctx.http, &ctx.data, &[], guild_id).await;
+}
+
+fn create_round_embed(round: usize, total: usize) -> CreateEmbed {
+    let prompt = match *QUIZ_CHAT_ANSWERS {
+        true => QUIZ_ROUND_PROMPT_CHAT,
+        false => QUIZ_ROUND_PROMPT,
+    };
+
+    CreateEmbed::default()
+        .title(format!(
+            "{} {} {} {}",
+            QUIZ_ROUND, round, QUIZ_ROUND_OF, total
+        ))
+        .description(prompt)
+}
+
+fn build_choice_btns(choices: &[String]) -> CreateActionRow {
+    let buttons = choices
+        .iter()
+        .enumerate()
+        .map(|(idx, choice)| {
+            // button labels are capped at 80 characters
+            CreateButton::new(format!("{}{}", QUIZ_CHOICE_PREFIX, idx))
+                .label(truncate_text(choice, 80))
+                .style(ButtonStyle::Secondary)
+        })
+        .collect();
+
+    CreateActionRow::Buttons(buttons)
+}
+
+fn create_scoreboard_embed(ranking: &[(UserId, u32)]) -> CreateEmbed {
+    let mut description = String::new();
+
+    for (idx, (user_id, score)) in ranking.iter().enumerate() {
+        let _ = writeln!(
+            description,
+            "`{}.` {} — **{}** point(s)",
+            idx + 1,
+            user_id.mention(),
+            score
+        );
+    }
+
+    if ranking.is_empty() {
+        description.push_str(QUIZ_NO_SCORES);
+    }
+
+    CreateEmbed::default()
+        .title(QUIZ_SCOREBOARD)
+        .description(description)
+}
+
+/// Where a clip starts: about halfway through what's left once there's room for a whole
+/// round, and never past the first minute. Tracks too short for that start at the beginning.
+pub fn get_clip_start(duration: Option<Duration>) -> Duration {
+    match duration {
+        Some(duration) if duration > ROUND_LENGTH => {
+            ((duration - ROUND_LENGTH) / 2).min(MAX_CLIP_START)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+/// Lowercases text and keeps only its letters and digits, one space between words.
+pub fn normalize_answer(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Everything that counts as a right answer for a track: its title as is and in the
+/// simpler shapes searches fall back to, and whoever's credited for it.
+pub fn get_quiz_answers(title: &str, artist: Option<&str>) -> Vec<String> {
+    let credited = title.split_once(" - ").map(|(credited, _)| credited);
+
+    let mut answers: Vec<String> = Vec::new();
+    let candidates = std::iter::once(title.to_string())
+        .chain(get_requery_shapes(title))
+        .chain(credited.map(|credited| credited.to_string()))
+        .chain(artist.map(|artist| artist.to_string()));
+
+    for candidate in candidates {
+        let answer = normalize_answer(&candidate);
+        if !answer.is_empty() && !answers.contains(&answer) {
+            answers.push(answer);
+        }
+    }
+
+    answers
+}
+
+/// Whether a guess is close enough to any of the answers, forgiving the odd typo.
+pub fn is_correct_answer(guess: &str, answers: &[String]) -> bool {
+    let guess = normalize_answer(guess);
+
+    if guess.is_empty() {
+        return false;
+    }
+
+    answers
+        .iter()
+        .any(|answer| get_similarity(&guess, answer) >= MIN_ANSWER_SIMILARITY)
+}
+
+/// How alike two strings are, from 0 to 1, going by how many edits it takes to turn
+/// one into the other.
+pub fn get_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            let deletion = previous[j + 1] + 1;
+            let insertion = current[j] + 1;
+            current.push(substitution.min(deletion).min(insertion));
+        }
+
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Picks the titles a round's buttons show, the right one among decoys from the rest
+/// of the playlist. Returns them in a random order along with where the right one ended up.
+pub fn pick_choices(answer: &str, pool: &[String], count: usize) -> (Vec<String>, usize) {
+    let mut rng = rand::thread_rng();
+
+    let mut decoys: Vec<&String> = pool.iter().filter(|title| *title != answer).collect();
+    decoys.sort();
+    decoys.dedup();
+
+    let mut choices: Vec<String> = decoys
+        .choose_multiple(&mut rng, count.saturating_sub(1))
+        .map(|title| title.to_string())
+        .collect();
+
+    choices.push(answer.to_string());
+    choices.shuffle(&mut rng);
+
+    let correct = choices.iter().position(|choice| choice == answer).unwrap();
+    (choices, correct)
+}
+
+/// Orders the players from most to fewest points.
+pub fn rank_scores(scores: &HashMap<UserId, u32>) -> Vec<(UserId, u32)> {
+    let mut ranking: Vec<(UserId, u32)> = scores
+        .iter()
+        .map(|(user_id, score)| (*user_id, *score))
+        .collect();
+
+    ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranking
+}
@@ -1,4 +1,5 @@
 use crate::{
+    commands::undo::save_undo_snapshot,
     errors::{ParrotError, verify},
     guild::stored_queue::GuildStoredQueueMap,
     handlers::track_end::update_queue_messages,
@@ -48,6 +49,9 @@ pub async fn remove(
         ),
     )?;
 
+    let (from, until) = (remove_index as usize, remove_until as usize);
+    save_undo_snapshot(&ctx.data, guild_id, "remove", &queue, from..until + 1, true).await;
+
     let track = queue.get(remove_index as usize).unwrap();
     let mut data = ctx.data.write().await;
     let guild_stored_queue = data
@@ -83,18 +87,22 @@ async fn create_remove_enqueued_embed(
     track: &TrackHandle,
     data: &Arc<RwLock<TypeMap>>,
 ) -> Result<CreateEmbed, ParrotError> {
-    let embed = CreateEmbed::default();
+    let mut embed = CreateEmbed::default();
     let metadata = get_track_metadata(track, data).await?;
 
-    Ok(embed
-        .field(
-            REMOVED_QUEUE,
-            format!(
-                "[**{}**]({})",
-                metadata.title.unwrap(),
-                metadata.source_url.unwrap()
-            ),
-            false,
-        )
-        .thumbnail(metadata.thumbnail.unwrap()))
+    embed = embed.field(
+        REMOVED_QUEUE,
+        format!(
+            "[**{}**]({})",
+            metadata.title.unwrap(),
+            metadata.source_url.unwrap()
+        ),
+        false,
+    );
+
+    if let Some(thumbnail) = metadata.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    Ok(embed)
 }
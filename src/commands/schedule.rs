@@ -0,0 +1,204 @@
+use crate::{
+    errors::{ParrotError, verify},
+    guild::schedule::{GuildSchedules, GuildSchedulesMap, SECONDS_PER_DAY, SECONDS_PER_WEEK},
+    handlers::schedule::{arm_schedule, disarm_schedule, get_unix_time},
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_NO_SCHEDULE, FAIL_SCHEDULE_LIMIT, FAIL_SCHEDULE_TIME},
+    },
+    utils::create_response,
+};
+use serenity::{
+    all::{CommandDataOption, CommandDataOptionValue, CommandInteraction},
+    client::Context,
+    prelude::Mentionable,
+};
+
+/// How many schedules a guild can have at once.
+const MAX_SCHEDULES: usize = 10;
+
+pub async fn schedule(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let subcommand = interaction.data.options.first().unwrap().clone();
+
+    let CommandDataOptionValue::SubCommand(args) = subcommand.value else {
+        unreachable!()
+    };
+
+    match subcommand.name.as_str() {
+        "add" => add(ctx, interaction, &args).await,
+        "list" => list(ctx, interaction).await,
+        "remove" => remove(ctx, interaction, &args).await,
+        _ => unreachable!(),
+    }
+}
+
+async fn add(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let get_arg = |name: &str| {
+        args.iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| &arg.value)
+    };
+
+    let channel_id = get_arg("channel")
+        .and_then(|value| value.as_channel_id())
+        .unwrap();
+    let query = get_arg("query")
+        .and_then(|value| value.as_str())
+        .unwrap()
+        .to_string();
+    let weekday = get_arg("day")
+        .and_then(|value| value.as_i64())
+        .map(|weekday| weekday as u64);
+    let weekly = get_arg("weekly")
+        .and_then(|value| value.as_bool())
+        .unwrap_or_default();
+
+    let time_of_day = get_arg("time")
+        .and_then(|value| value.as_str())
+        .and_then(parse_time_of_day);
+    let time_of_day = verify(time_of_day, ParrotError::Other(FAIL_SCHEDULE_TIME))?;
+
+    let next_run = next_occurrence(get_unix_time(), time_of_day, weekday);
+
+    let schedule = {
+        let mut data = ctx.data.write().await;
+        let guild_schedules = data
+            .get_mut::<GuildSchedulesMap>()
+            .unwrap()
+            .entry(guild_id)
+            .or_insert_with(|| GuildSchedules::new(guild_id));
+
+        verify(
+            guild_schedules.schedules.len() < MAX_SCHEDULES,
+            ParrotError::Other(FAIL_SCHEDULE_LIMIT),
+        )?;
+
+        let schedule =
+            guild_schedules.add(channel_id, query, next_run, weekly, interaction.user.id);
+        guild_schedules.save()?;
+        schedule
+    };
+
+    arm_schedule(ctx, guild_id, &schedule).await;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::ScheduleAdded {
+            id: schedule.id,
+            channel: channel_id.mention(),
+            at: next_run,
+            weekly,
+        },
+    )
+    .await
+}
+
+async fn list(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let schedules = {
+        let data = ctx.data.read().await;
+        data.get::<GuildSchedulesMap>()
+            .unwrap()
+            .get(&guild_id)
+            .map(|guild_schedules| guild_schedules.schedules.clone())
+            .unwrap_or_default()
+    };
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::Schedules { schedules },
+    )
+    .await
+}
+
+async fn remove(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let id = args
+        .first()
+        .and_then(|arg| arg.value.as_i64())
+        .and_then(|id| u32::try_from(id).ok())
+        .unwrap_or_default();
+
+    let removed = {
+        let mut data = ctx.data.write().await;
+        match data
+            .get_mut::<GuildSchedulesMap>()
+            .unwrap()
+            .get_mut(&guild_id)
+        {
+            Some(guild_schedules) => {
+                let removed = guild_schedules.remove(id);
+                guild_schedules.save()?;
+                removed
+            }
+            None => None,
+        }
+    };
+
+    verify(removed, ParrotError::Other(FAIL_NO_SCHEDULE))?;
+    disarm_schedule(ctx, guild_id, id).await;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::ScheduleRemoved { id },
+    )
+    .await
+}
+
+/// Reads a time of day like `21:30`, in UTC, as seconds since midnight.
+pub fn parse_time_of_day(text: &str) -> Option<u64> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+
+    if hours.is_empty() || hours.len() > 2 || minutes.len() != 2 {
+        return None;
+    }
+
+    let hours = hours.parse::<u64>().ok().filter(|hours| *hours < 24)?;
+    let minutes = minutes
+        .parse::<u64>()
+        .ok()
+        .filter(|minutes| *minutes < 60)?;
+
+    Some(hours * 60 * 60 + minutes * 60)
+}
+
+/// The first time after `now` that falls on the given time of day, on the given weekday if any.
+/// Weekdays count from Monday as 0, and all times are seconds since the Unix epoch in UTC.
+pub fn next_occurrence(now: u64, time_of_day: u64, weekday: Option<u64>) -> u64 {
+    let today = now / SECONDS_PER_DAY;
+    let mut next_run = today * SECONDS_PER_DAY + time_of_day;
+
+    match weekday {
+        Some(weekday) => {
+            // the epoch fell on a thursday
+            let today_weekday = (today + 3) % 7;
+            next_run += (weekday % 7 + 7 - today_weekday) % 7 * SECONDS_PER_DAY;
+
+            if next_run <= now {
+                next_run += SECONDS_PER_WEEK;
+            }
+        }
+        None if next_run <= now => next_run += SECONDS_PER_DAY,
+        None => {}
+    }
+
+    next_run
+}
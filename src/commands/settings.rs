@@ -0,0 +1,451 @@
+use crate::{
+    commands::summon::rejoin_home_channel,
+    connection::get_voice_channel_for_user,
+    errors::{ParrotError, verify},
+    guild::settings::{
+        DEFAULT_SKIP_THRESHOLD, DuplicatePolicy, GuildSettings, GuildSettingsMap, PermissionLevel,
+        TrackFallback,
+    },
+    messaging::{message::ParrotMessage, messages::FAIL_UNKNOWN_COMMAND},
+    permissions::default_permission_level,
+    utils::create_response,
+};
+use serenity::{
+    all::{CommandDataOption, CommandDataOptionValue, CommandInteraction},
+    client::Context,
+    prelude::Mentionable,
+};
+use std::str::FromStr;
+
+pub async fn settings(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let subcommand = interaction.data.options.first().unwrap().clone();
+
+    let args = match subcommand.value {
+        CommandDataOptionValue::SubCommand(args) => args,
+        CommandDataOptionValue::SubCommandGroup(args) => {
+            return match subcommand.name.as_str() {
+                "channel" => channel(ctx, interaction, &args).await,
+                _ => unreachable!(),
+            };
+        }
+        _ => unreachable!(),
+    };
+
+    match subcommand.name.as_str() {
+        "alwayson" => always_on(ctx, interaction, &args).await,
+        "announce" => announce(ctx, interaction, &args).await,
+        "djrole" => dj_role(ctx, interaction, &args).await,
+        "duplicates" => duplicates(ctx, interaction, &args).await,
+        "fallback" => fallback(ctx, interaction, &args).await,
+        "permission" => permission(ctx, interaction, &args).await,
+        "stage" => stage(ctx, interaction, &args).await,
+        "timeouts" => timeouts(ctx, interaction, &args).await,
+        "voteskip" => vote_skip(ctx, interaction, &args).await,
+        _ => unreachable!(),
+    }
+}
+
+async fn always_on(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let get_arg = |name: &str| {
+        args.iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| &arg.value)
+    };
+
+    let enabled = get_arg("enabled")
+        .and_then(|value| value.as_bool())
+        .unwrap_or_default();
+    let playlist = get_arg("playlist")
+        .and_then(|value| value.as_str())
+        .map(|playlist| playlist.to_string());
+
+    let home_channel = get_arg("channel")
+        .and_then(|value| value.as_channel_id())
+        .or_else(|| {
+            let guild = ctx.cache.guild(guild_id)?;
+            get_voice_channel_for_user(&guild, &interaction.user.id)
+        });
+
+    verify(
+        !enabled || home_channel.is_some(),
+        ParrotError::AuthorNotFound,
+    )?;
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    guild_settings.always_on = enabled;
+
+    if home_channel.is_some() {
+        guild_settings.home_channel = home_channel;
+    }
+
+    if playlist.is_some() {
+        guild_settings.fallback_playlist = playlist;
+    }
+
+    let home_channel = guild_settings.home_channel.filter(|_| enabled);
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsAlwaysOn {
+            channel: home_channel.map(|channel_id| channel_id.mention()),
+        },
+    )
+    .await?;
+
+    let manager = songbird::get(ctx).await.unwrap();
+    let is_connected = match manager.get(guild_id) {
+        Some(call) => call.lock().await.current_channel().is_some(),
+        None => false,
+    };
+
+    if enabled && !is_connected {
+        rejoin_home_channel(ctx, guild_id).await?;
+    }
+
+    Ok(())
+}
+
+async fn announce(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    // leaving the channel out turns announcements off
+    let channel = args.first().and_then(|arg| arg.value.as_channel_id());
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.announce_channel = channel;
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsAnnounce {
+            channel: channel.map(|channel_id| channel_id.mention()),
+        },
+    )
+    .await
+}
+
+async fn channel(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    group: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let subcommand = group.first().unwrap();
+
+    let channel_id = match &subcommand.value {
+        CommandDataOptionValue::SubCommand(args) => {
+            args.first().and_then(|arg| arg.value.as_channel_id())
+        }
+        _ => None,
+    };
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    match (subcommand.name.as_str(), channel_id) {
+        ("add", Some(channel_id)) => {
+            guild_settings.bound_channels.insert(channel_id);
+            guild_settings.save()?;
+        }
+        ("remove", Some(channel_id)) => {
+            guild_settings.bound_channels.remove(&channel_id);
+            guild_settings.save()?;
+        }
+        _ => {}
+    }
+
+    let channels = guild_settings
+        .bound_channels
+        .iter()
+        .map(|channel_id| channel_id.mention())
+        .collect();
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsChannels { channels },
+    )
+    .await
+}
+
+async fn fallback(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let fallback = args
+        .first()
+        .and_then(|arg| arg.value.as_str())
+        .and_then(|mode| TrackFallback::from_str(mode).ok())
+        .unwrap_or_default();
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.track_fallback = fallback;
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsFallback { fallback },
+    )
+    .await
+}
+
+async fn duplicates(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let policy = args
+        .first()
+        .and_then(|arg| arg.value.as_str())
+        .and_then(|policy| DuplicatePolicy::from_str(policy).ok())
+        .unwrap_or_default();
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.duplicate_policy = policy;
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsDuplicates { policy },
+    )
+    .await
+}
+
+async fn dj_role(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let role = args.first().and_then(|arg| arg.value.as_role_id());
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.dj_role = role;
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsDjRole {
+            role: role.map(|role| role.mention()),
+        },
+    )
+    .await
+}
+
+async fn permission(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let get_arg = |name: &str| {
+        args.iter()
+            .find(|arg| arg.name == name)
+            .and_then(|arg| arg.value.as_str())
+    };
+
+    let command = get_arg("command")
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_lowercase();
+
+    verify(
+        default_permission_level(&command),
+        ParrotError::Other(FAIL_UNKNOWN_COMMAND),
+    )?;
+
+    // anything that isn't a level goes back to the default
+    let level = get_arg("level").and_then(|level| PermissionLevel::from_str(level).ok());
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    match level {
+        Some(level) => guild_settings
+            .command_permissions
+            .insert(command.clone(), level),
+        None => guild_settings.command_permissions.remove(&command),
+    };
+
+    let level = guild_settings.get_permission_level(&command);
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsPermission {
+            cmd: command,
+            level,
+        },
+    )
+    .await
+}
+
+async fn stage(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let topic = args
+        .first()
+        .and_then(|arg| arg.value.as_bool())
+        .unwrap_or_default();
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.stage_topic = topic;
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsStage { topic },
+    )
+    .await
+}
+
+async fn timeouts(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let get_arg = |name: &str| {
+        args.iter()
+            .find(|arg| arg.name == name)
+            .and_then(|arg| arg.value.as_i64())
+            .map(|value| value.max(0) as u64)
+    };
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    // options that were left out keep their current value
+    if let Some(minutes) = get_arg("idle") {
+        guild_settings.idle_timeout = minutes * 60;
+    }
+
+    if let Some(seconds) = get_arg("alone") {
+        guild_settings.alone_timeout = seconds;
+    }
+
+    let (idle, alone) = (guild_settings.idle_timeout, guild_settings.alone_timeout);
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsTimeouts { idle, alone },
+    )
+    .await
+}
+
+async fn vote_skip(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    args: &[CommandDataOption],
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let threshold = args
+        .first()
+        .and_then(|arg| arg.value.as_i64())
+        .map(|percent| percent.clamp(1, 100) as u8)
+        .unwrap_or(DEFAULT_SKIP_THRESHOLD);
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.skip_threshold = threshold;
+    guild_settings.save()?;
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SettingsVoteSkip { threshold },
+    )
+    .await
+}
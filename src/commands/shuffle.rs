@@ -1,9 +1,11 @@
 use crate::{
-    errors::ParrotError, handlers::track_end::update_queue_messages,
-    messaging::message::ParrotMessage, utils::create_response,
+    commands::undo::save_undo_snapshot, errors::ParrotError,
+    handlers::track_end::update_queue_messages, messaging::message::ParrotMessage,
+    utils::create_response,
 };
 use rand::Rng;
 use serenity::{all::CommandInteraction, client::Context};
+use songbird::tracks::TrackQueue;
 
 pub async fn shuffle(
     ctx: &Context,
@@ -14,13 +16,9 @@ pub async fn shuffle(
     let call = manager.get(guild_id).unwrap();
 
     let handler = call.lock().await;
-    handler.queue().modify_queue(|queue| {
-        // skip the first track on queue because it's being played
-        fisher_yates(
-            queue.make_contiguous()[1..].as_mut(),
-            &mut rand::thread_rng(),
-        )
-    });
+    let queue = handler.queue().current_queue();
+    save_undo_snapshot(&ctx.data, guild_id, "shuffle", &queue, 0..0, false).await;
+    shuffle_upcoming(handler.queue());
 
     // refetch the queue after modification
     let queue = handler.queue().current_queue();
@@ -31,6 +29,16 @@ pub async fn shuffle(
     Ok(())
 }
 
+pub fn shuffle_upcoming(queue: &TrackQueue) {
+    queue.modify_queue(|queue| {
+        // skip the first track on queue because it's being played
+        fisher_yates(
+            queue.make_contiguous()[1..].as_mut(),
+            &mut rand::thread_rng(),
+        )
+    });
+}
+
 fn fisher_yates<T, R>(values: &mut [T], mut rng: R)
 where
     R: rand::RngCore + Sized,
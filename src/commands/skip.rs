@@ -40,27 +40,26 @@ pub async fn create_skip_response(
     handler: &MutexGuard<'_, Call>,
     tracks_to_skip: usize,
 ) -> Result<(), ParrotError> {
+    let message = get_skip_message(ctx, handler, tracks_to_skip).await?;
+    create_response(&ctx.http, interaction, message).await
+}
+
+pub async fn get_skip_message(
+    ctx: &Context,
+    handler: &MutexGuard<'_, Call>,
+    tracks_to_skip: usize,
+) -> Result<ParrotMessage, ParrotError> {
     match handler.queue().current() {
         Some(track) => {
             let metadata = get_track_metadata(&track, &ctx.data).await?;
 
-            create_response(
-                &ctx.http,
-                interaction,
-                ParrotMessage::SkipTo {
-                    title: metadata.title.unwrap(),
-                    url: metadata.source_url.unwrap(),
-                },
-            )
-            .await
-        }
-        None => {
-            if tracks_to_skip > 1 {
-                create_response(&ctx.http, interaction, ParrotMessage::SkipAll).await
-            } else {
-                create_response(&ctx.http, interaction, ParrotMessage::Skip).await
-            }
+            Ok(ParrotMessage::SkipTo {
+                title: metadata.title.unwrap(),
+                url: metadata.source_url.unwrap(),
+            })
         }
+        None if tracks_to_skip > 1 => Ok(ParrotMessage::SkipAll),
+        None => Ok(ParrotMessage::Skip),
     }
 }
 
@@ -0,0 +1,105 @@
+use crate::{
+    errors::{ParrotError, verify},
+    handlers::sleep::{cancel_sleep_timer, sleep_after_track, start_sleep_countdown},
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_NO_SLEEP_TIMER, FAIL_SLEEP_DURATION},
+    },
+    utils::create_response,
+};
+use serenity::{
+    all::{CommandDataOptionValue, CommandInteraction},
+    client::Context,
+};
+use std::time::{Duration, SystemTime};
+
+/// Nobody needs Parrot to stay up for longer than a day.
+const MAX_SLEEP_DURATION: Duration = Duration::from_secs(60 * 60 * 24);
+
+pub async fn sleep(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let subcommand = interaction.data.options.first().unwrap().clone();
+
+    let CommandDataOptionValue::SubCommand(args) = subcommand.value else {
+        unreachable!()
+    };
+
+    let message = match subcommand.name.as_str() {
+        "in" => {
+            let duration = args
+                .first()
+                .and_then(|arg| arg.value.as_str())
+                .and_then(parse_duration)
+                .filter(|duration| *duration <= MAX_SLEEP_DURATION);
+
+            let duration = verify(duration, ParrotError::Other(FAIL_SLEEP_DURATION))?;
+            start_sleep_countdown(ctx, guild_id, duration).await;
+
+            ParrotMessage::SleepIn {
+                at: SystemTime::now() + duration,
+            }
+        }
+        "aftertrack" => {
+            let manager = songbird::get(ctx).await.unwrap();
+            let call = manager.get(guild_id).unwrap();
+
+            let track = call
+                .lock()
+                .await
+                .queue()
+                .current()
+                .ok_or(ParrotError::NothingPlaying)?;
+
+            sleep_after_track(ctx, guild_id, &track).await;
+            ParrotMessage::SleepAfterTrack
+        }
+        "cancel" => {
+            let cancelled = cancel_sleep_timer(&ctx.data, guild_id).await;
+            verify(cancelled, ParrotError::Other(FAIL_NO_SLEEP_TIMER))?;
+            ParrotMessage::SleepCancel
+        }
+        _ => unreachable!(),
+    };
+
+    create_response(&ctx.http, interaction, message).await
+}
+
+/// Reads durations like `1h30m`, `45m` or `90s`, where a bare number counts as minutes.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+
+    if let Ok(minutes) = text.parse::<u64>() {
+        return minutes
+            .checked_mul(60)
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs);
+    }
+
+    let mut seconds: u64 = 0;
+    let mut number = String::new();
+
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'h' => 60 * 60,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+
+        let value = number.parse::<u64>().ok()?;
+        seconds = seconds.checked_add(value.checked_mul(unit)?)?;
+        number.clear();
+    }
+
+    // a trailing number without a unit is ambiguous next to the others
+    if !number.is_empty() || seconds == 0 {
+        return None;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
@@ -0,0 +1,27 @@
+use crate::{
+    errors::ParrotError,
+    messaging::message::ParrotMessage,
+    sources::spotify::{SPOTIFY_STATUS, Spotify},
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn spotify(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let subcommand = interaction.data.options.first().unwrap().name.as_str();
+
+    if subcommand == "reconnect" {
+        Spotify::reconnect();
+        return create_response(&ctx.http, interaction, ParrotMessage::SpotifyReconnect).await;
+    }
+
+    let status = SPOTIFY_STATUS.lock().await.clone();
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::SpotifyStatus { status },
+    )
+    .await
+}
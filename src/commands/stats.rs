@@ -0,0 +1,100 @@
+use crate::{
+    errors::{ParrotError, verify},
+    guild::{
+        play_log::{PLAY_LOG_RETENTION, PlayStats, load_play_log},
+        schedule::SECONDS_PER_DAY,
+    },
+    handlers::schedule::get_unix_time,
+    messaging::messages::{
+        FAIL_NO_STATS, STATS_BUSIEST, STATS_LISTENED, STATS_TITLE, STATS_TITLE_ME,
+        STATS_TOP_REQUESTERS, STATS_TOP_TRACKS,
+    },
+    utils::create_embed_response,
+};
+use serenity::{
+    all::CommandInteraction, builder::CreateEmbed, client::Context, prelude::Mentionable,
+};
+use std::fmt::Write;
+
+const WEEKDAYS: [&str; 7] = [
+    "Mondays",
+    "Tuesdays",
+    "Wednesdays",
+    "Thursdays",
+    "Fridays",
+    "Saturdays",
+    "Sundays",
+];
+
+pub async fn stats(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let subcommand = interaction.data.options.first().unwrap().name.clone();
+
+    let mut entries = load_play_log(guild_id, get_unix_time())?;
+
+    let title = match subcommand.as_str() {
+        "server" => STATS_TITLE.to_string(),
+        "me" => {
+            let user_id = interaction.user.id;
+            entries.retain(|entry| entry.requester == Some(user_id));
+            format!("{} {}", STATS_TITLE_ME, interaction.user.name)
+        }
+        _ => unreachable!(),
+    };
+
+    verify(!entries.is_empty(), ParrotError::Other(FAIL_NO_STATS))?;
+
+    let stats = PlayStats::new(&entries);
+    let embed = create_stats_embed(title, &stats, subcommand == "server");
+    create_embed_response(&ctx.http, interaction, embed).await
+}
+
+fn create_stats_embed(title: String, stats: &PlayStats, show_requesters: bool) -> CreateEmbed {
+    let hours = stats.play_time as f64 / 3600.0;
+    let days = PLAY_LOG_RETENTION / SECONDS_PER_DAY;
+
+    let mut embed = CreateEmbed::default().title(title).description(format!(
+        "**{}** track(s) and **{:.1}** hour(s) {} {} days.",
+        stats.plays, hours, STATS_LISTENED, days
+    ));
+
+    let mut top_tracks = String::new();
+    for (idx, track) in stats.top_tracks.iter().enumerate() {
+        let title = match &track.source_url {
+            Some(source_url) => format!("[{}]({})", track.title, source_url),
+            None => track.title.clone(),
+        };
+        let _ = writeln!(
+            top_tracks,
+            "`{}.` {} — **{}** play(s)",
+            idx + 1,
+            title,
+            track.plays
+        );
+    }
+    embed = embed.field(STATS_TOP_TRACKS, top_tracks, false);
+
+    if show_requesters && !stats.top_requesters.is_empty() {
+        let mut top_requesters = String::new();
+        for (idx, (user_id, plays)) in stats.top_requesters.iter().enumerate() {
+            let _ = writeln!(
+                top_requesters,
+                "`{}.` {} — **{}** track(s)",
+                idx + 1,
+                user_id.mention(),
+                plays
+            );
+        }
+        embed = embed.field(STATS_TOP_REQUESTERS, top_requesters, false);
+    }
+
+    if let (Some(weekday), Some(hour)) = (stats.busiest_weekday, stats.busiest_hour) {
+        let busiest = format!(
+            "{}, and around {:02}:00 UTC",
+            WEEKDAYS[weekday as usize], hour
+        );
+        embed = embed.field(STATS_BUSIEST, busiest, false);
+    }
+
+    embed
+}
@@ -1,17 +1,37 @@
 use crate::{
     errors::{ParrotError, verify},
     guild::stored_queue::GuildStoredQueueMap,
-    handlers::track_end::update_queue_messages,
+    handlers::{track_end::update_queue_messages, track_error::forget_restarted_tracks},
     messaging::message::ParrotMessage,
     utils::create_response,
 };
-use serenity::{all::CommandInteraction, client::Context};
+use serenity::{
+    all::{CommandInteraction, GuildId},
+    client::Context,
+    prelude::Mutex,
+};
+use songbird::{Call, tracks::TrackHandle};
+use std::sync::Arc;
 
 pub async fn stop(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
     let guild_id = interaction.guild_id.unwrap();
     let manager = songbird::get(ctx).await.unwrap();
     let call = manager.get(guild_id).unwrap();
 
+    let queue = stop_playback(ctx, guild_id, &call).await?;
+
+    create_response(&ctx.http, interaction, ParrotMessage::Stop).await?;
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+/// Stops the current track and throws the rest of the queue away.
+/// Returns what's left in the queue.
+pub async fn stop_playback(
+    ctx: &Context,
+    guild_id: GuildId,
+    call: &Arc<Mutex<Call>>,
+) -> Result<Vec<TrackHandle>, ParrotError> {
     let mut data = ctx.data.write().await;
     let stored_queue = data.get_mut::<GuildStoredQueueMap>().unwrap();
     let guild_stored_queue = stored_queue.get_mut(&guild_id).unwrap();
@@ -23,13 +43,13 @@ pub async fn stop(ctx: &Context, interaction: &mut CommandInteraction) -> Result
     let queue = handler.queue();
 
     verify(!queue.is_empty(), ParrotError::NothingPlaying)?;
+    let stopped = queue.current_queue();
     queue.stop();
 
     // refetch the queue after modification
     let queue = handler.queue().current_queue();
     drop(handler);
 
-    create_response(&ctx.http, interaction, ParrotMessage::Stop).await?;
-    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
-    Ok(())
+    forget_restarted_tracks(&ctx.data, guild_id, &stopped).await;
+    Ok(queue)
 }
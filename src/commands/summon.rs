@@ -1,17 +1,21 @@
 use crate::{
-    connection::get_voice_channel_for_user,
-    errors::ParrotError,
-    handlers::{IdleHandler, TrackEndHandler},
+    commands::play::play_fallback_playlist,
+    connection::{get_stage_permissions, get_voice_channel_for_user, join_stage},
+    errors::{ParrotError, verify},
+    guild::{cache::GuildCacheMap, settings::GuildSettingsMap},
+    handlers::{
+        DriverDisconnectHandler, IdleHandler, TrackEndHandler, TrackErrorHandler, TrackStartHandler,
+    },
     messaging::message::ParrotMessage,
     utils::create_response,
 };
 use serenity::{
-    all::{ChannelId, CommandInteraction},
+    all::{ChannelId, CommandInteraction, CreateEmbed, CreateMessage, GuildId},
     client::Context,
-    prelude::Mentionable,
+    prelude::{Mentionable, Mutex},
 };
-use songbird::{Event, TrackEvent};
-use std::time::Duration;
+use songbird::{Call, CoreEvent, Event, TrackEvent};
+use std::{sync::Arc, time::Duration};
 
 pub async fn summon(
     ctx: &Context,
@@ -36,53 +40,229 @@ pub async fn summon(
         }
     }
 
+    join_channel(ctx, guild_id, channel_id, interaction.channel_id).await?;
+
+    if send_reply {
+        return create_response(
+            &ctx.http,
+            interaction,
+            ParrotMessage::Summon {
+                mention: channel_id.mention(),
+            },
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Joins a voice channel and registers the handlers every call needs.
+/// Notices about the call, e.g. tracks failing, are posted to the given text channel.
+pub async fn join_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    text_channel_id: ChannelId,
+) -> Result<Arc<Mutex<Call>>, ParrotError> {
+    let manager = songbird::get(ctx).await.unwrap();
+
+    // an audience member can't be heard, so don't bother joining a stage as one
+    let stage_permissions = get_stage_permissions(ctx, guild_id, channel_id).await;
+    if let Some(permissions) = stage_permissions {
+        verify(
+            permissions.mute_members() || permissions.request_to_speak(),
+            ParrotError::MissingStagePermission,
+        )?;
+    }
+
+    // recorded up front, as the voice state update can beat the join to completion and
+    // shouldn't be taken for somebody dragging parrot around
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+    cache.voice_channel = Some(channel_id);
+    cache.text_channel = Some(text_channel_id);
+    drop(data);
+
     // join channel
-    match manager.join(guild_id, channel_id).await {
-        Ok(_) => {}
+    let call = match manager.join(guild_id, channel_id).await {
+        Ok(call) => call,
         Err(err) => {
             let _ = manager.remove(guild_id).await;
             println!("{:?}", err);
-            return Err::<(), ParrotError>(ParrotError::Join(Box::new(err)));
+            return Err(ParrotError::Join(Box::new(err)));
         }
+    };
+
+    // a fresh connection hasn't been asked to leave yet
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    cache_map.entry(guild_id).or_default().expected_disconnect = false;
+    drop(data);
+
+    if let Some(permissions) = stage_permissions {
+        join_stage(ctx, guild_id, channel_id, permissions).await?;
     }
 
-    // register events
-    if let Some(call) = manager.get(guild.id) {
-        let mut handler = call.lock().await;
-
-        handler.remove_all_global_events();
-
-        handler.add_global_event(
-            Event::Periodic(Duration::from_secs(1), None),
-            IdleHandler {
-                http: ctx.http.clone(),
-                manager,
-                interaction: interaction.clone(),
-                limit: 60 * 10,
-                count: Default::default(),
-            },
-        );
+    register_handlers(ctx, &call, guild_id, text_channel_id).await;
 
-        handler.add_global_event(
-            Event::Track(TrackEvent::End),
-            TrackEndHandler {
-                http: ctx.http.clone(),
-                guild_id: guild.id,
-                call: call.clone(),
-                ctx_data: ctx.data.clone(),
-            },
+    Ok(call)
+}
+
+/// Replaces the handlers on a call with a fresh set, posting notices to the given text channel.
+pub async fn register_handlers(
+    ctx: &Context,
+    call: &Arc<Mutex<Call>>,
+    guild_id: GuildId,
+    text_channel_id: ChannelId,
+) {
+    let manager = songbird::get(ctx).await.unwrap();
+    let mut handler = call.lock().await;
+
+    handler.remove_all_global_events();
+
+    handler.add_global_event(
+        Event::Periodic(Duration::from_secs(1), None),
+        IdleHandler {
+            http: ctx.http.clone(),
+            manager,
+            guild_id,
+            channel_id: text_channel_id,
+            ctx_data: ctx.data.clone(),
+            count: Default::default(),
+        },
+    );
+
+    handler.add_global_event(
+        Event::Track(TrackEvent::End),
+        TrackEndHandler {
+            http: ctx.http.clone(),
+            guild_id,
+            call: call.clone(),
+            ctx_data: ctx.data.clone(),
+        },
+    );
+
+    handler.add_global_event(
+        Event::Track(TrackEvent::Error),
+        TrackErrorHandler {
+            http: ctx.http.clone(),
+            guild_id,
+            channel_id: text_channel_id,
+            call: call.clone(),
+            ctx_data: ctx.data.clone(),
+        },
+    );
+
+    handler.add_global_event(
+        Event::Track(TrackEvent::Play),
+        TrackStartHandler {
+            ctx: ctx.clone(),
+            guild_id,
+        },
+    );
+
+    handler.add_global_event(
+        Event::Core(CoreEvent::DriverDisconnect),
+        DriverDisconnectHandler {
+            ctx: ctx.clone(),
+            guild_id,
+            channel_id: text_channel_id,
+        },
+    );
+}
+
+/// Keeps playing after a moderator drags Parrot into another voice channel, pointing the
+/// call's handlers at the right place and letting the guild know where the music went.
+pub async fn follow_channel_move(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<(), ParrotError> {
+    let manager = songbird::get(ctx).await.unwrap();
+    let Some(call) = manager.get(guild_id) else {
+        return Ok(());
+    };
+
+    let text_channel_id = {
+        let mut data = ctx.data.write().await;
+        let bound_channels = data
+            .get::<GuildSettingsMap>()
+            .unwrap()
+            .get(&guild_id)
+            .map(|guild_settings| guild_settings.bound_channels.clone())
+            .unwrap_or_default();
+
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let cache = cache_map.entry(guild_id).or_default();
+
+        // parrot joined this channel itself
+        if cache.voice_channel == Some(channel_id) {
+            return Ok(());
+        }
+
+        // notices belong in a bound channel, if the guild picked any
+        let text_channel_id = cache
+            .text_channel
+            .filter(|text_channel_id| {
+                bound_channels.is_empty() || bound_channels.contains(text_channel_id)
+            })
+            .or_else(|| bound_channels.iter().min().copied())
+            .unwrap_or(channel_id);
+
+        cache.voice_channel = Some(channel_id);
+        cache.text_channel = Some(text_channel_id);
+        text_channel_id
+    };
+
+    // being dragged onto a stage leaves parrot in the audience
+    if let Some(permissions) = get_stage_permissions(ctx, guild_id, channel_id).await
+        && let Err(err) = join_stage(ctx, guild_id, channel_id, permissions).await
+    {
+        println!(
+            "[ERROR] Failed to speak on the stage in guild {} due to {}",
+            guild_id, err
         );
     }
 
-    if send_reply {
-        return create_response(
-            &ctx.http,
-            interaction,
-            ParrotMessage::Summon {
-                mention: channel_id.mention(),
-            },
-        )
-        .await;
+    register_handlers(ctx, &call, guild_id, text_channel_id).await;
+
+    let embed = CreateEmbed::default().description(format!(
+        "{}",
+        ParrotMessage::Moved {
+            mention: channel_id.mention()
+        }
+    ));
+
+    text_channel_id
+        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+/// Brings the bot back to its home channel when the guild has 24/7 mode on, and gets the
+/// fallback playlist going if there's nothing left to play.
+pub async fn rejoin_home_channel(ctx: &Context, guild_id: GuildId) -> Result<(), ParrotError> {
+    let home_channel = {
+        let data = ctx.data.read().await;
+        data.get::<GuildSettingsMap>()
+            .unwrap()
+            .get(&guild_id)
+            .filter(|guild_settings| guild_settings.always_on)
+            .and_then(|guild_settings| guild_settings.home_channel)
+    };
+
+    let Some(home_channel) = home_channel else {
+        return Ok(());
+    };
+
+    // voice channels have their own text chat, which is where the listeners are
+    let call = join_channel(ctx, guild_id, home_channel, home_channel).await?;
+
+    let is_queue_empty = call.lock().await.queue().is_empty();
+    if is_queue_empty {
+        play_fallback_playlist(&call, &ctx.http, &ctx.data, guild_id).await?;
     }
 
     Ok(())
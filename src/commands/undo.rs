@@ -0,0 +1,245 @@
+use crate::{
+    commands::play::get_replay_source,
+    errors::{ParrotError, verify},
+    guild::{
+        cache::{GuildCacheMap, UndoSnapshot, UndoTrack},
+        http_client::HttpClientInstance,
+        metadata_store::MetadataStore,
+        stored_queue::GuildStoredQueueMap,
+    },
+    handlers::track_end::update_queue_messages,
+    messaging::{message::ParrotMessage, messages::FAIL_NOTHING_TO_UNDO},
+    utils::create_response,
+};
+use serenity::{
+    all::CommandInteraction,
+    client::Context,
+    model::id::GuildId,
+    prelude::{Mutex, RwLock, TypeMap},
+};
+use songbird::{Call, tracks::TrackHandle};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How long a destructive command can be undone for.
+const UNDO_TIMEOUT: u64 = 60 * 5;
+
+/// Where an upcoming track comes from when the queue is put back together.
+#[derive(Debug, PartialEq)]
+pub enum RestoredTrack {
+    /// Still queued, at this position among the upcoming tracks.
+    Queued(usize),
+    /// Gone from the queue, this is its position among the snapshot's tracks.
+    Missing(usize),
+}
+
+pub async fn undo(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let (snapshot, http_client) = {
+        let mut data = ctx.data.write().await;
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let snapshot = cache_map
+            .get_mut(&guild_id)
+            .and_then(|cache| cache.undo_snapshot.take());
+
+        (snapshot, data.get::<HttpClientInstance>().unwrap().clone())
+    };
+
+    let snapshot =
+        snapshot.filter(|snapshot| snapshot.taken_at.elapsed() < Duration::from_secs(UNDO_TIMEOUT));
+    let snapshot = verify(snapshot, ParrotError::Other(FAIL_NOTHING_TO_UNDO))?;
+
+    let queue = call.lock().await.queue().current_queue();
+    let upcoming: Vec<String> = queue
+        .iter()
+        .skip(1)
+        .map(|track| track.uuid().to_string())
+        .collect();
+
+    let upcoming_uuids: Vec<&str> = upcoming.iter().map(|uuid| uuid.as_str()).collect();
+    let plan = plan_restore(&snapshot.tracks, &upcoming_uuids);
+
+    // queue whatever went missing again, the queue gets put in order afterwards
+    let mut restored_uuids = HashMap::new();
+    let mut failed = 0;
+
+    for step in plan.iter() {
+        let RestoredTrack::Missing(idx) = step else {
+            continue;
+        };
+
+        match requeue_track(ctx, &call, &http_client, &snapshot.tracks[*idx]).await {
+            Ok(track) => {
+                restored_uuids.insert(*idx, track.uuid().to_string());
+            }
+            Err(err) => {
+                println!("[ERROR] Failed to restore a track in guild {guild_id}: {err}");
+                failed += 1;
+            }
+        }
+    }
+
+    let order: Vec<String> = plan
+        .iter()
+        .filter_map(|step| match step {
+            RestoredTrack::Queued(idx) => Some(upcoming[*idx].clone()),
+            RestoredTrack::Missing(idx) => restored_uuids.get(idx).cloned(),
+        })
+        .collect();
+
+    let handler = call.lock().await;
+    handler.queue().modify_queue(|queue| {
+        if queue.len() < 2 {
+            return;
+        }
+
+        // whatever's playing stays put, anything queued since goes after the restored tracks
+        let rank = |uuid: String| order.iter().position(|ordered| *ordered == uuid);
+        queue.make_contiguous()[1..]
+            .sort_by_key(|track| rank(track.uuid().to_string()).unwrap_or(usize::MAX));
+    });
+
+    let queue = handler.queue().current_queue();
+    drop(handler);
+
+    if let Some(stored_queue) = snapshot.stored_queue {
+        let mut data = ctx.data.write().await;
+        if let Some(guild_stored_queue) = data
+            .get_mut::<GuildStoredQueueMap>()
+            .unwrap()
+            .get_mut(&guild_id)
+        {
+            guild_stored_queue.queue = stored_queue;
+        }
+    }
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::Undo {
+            cmd: snapshot.command.to_string(),
+            failed,
+        },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+/// Remembers the upcoming tracks before a command reshapes the queue, replacing whatever
+/// could be undone before. `removed` holds the positions the command takes out of the queue,
+/// and commands that also change the stored queue ask for it to be kept as well.
+pub async fn save_undo_snapshot(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    command: &'static str,
+    queue: &[TrackHandle],
+    removed: Range<usize>,
+    save_stored_queue: bool,
+) {
+    let mut data = data.write().await;
+
+    let tracks = {
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+
+        queue
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(position, track)| {
+                let uuid = track.uuid().to_string();
+
+                UndoTrack {
+                    metadata: metadata_store.retrieve_metadata(&uuid).cloned(),
+                    requester: metadata_store.retrieve_requester(&uuid),
+                    removed: removed.contains(&position),
+                    uuid,
+                }
+            })
+            .collect()
+    };
+
+    let stored_queue = match save_stored_queue {
+        true => data
+            .get::<GuildStoredQueueMap>()
+            .unwrap()
+            .get(&guild_id)
+            .map(|guild_stored_queue| guild_stored_queue.queue.clone()),
+        false => None,
+    };
+
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    cache_map.entry(guild_id).or_default().undo_snapshot = Some(UndoSnapshot {
+        command,
+        tracks,
+        stored_queue,
+        taken_at: Instant::now(),
+    });
+}
+
+/// Works out the order of the upcoming tracks as they were in the snapshot, pointing out
+/// which are still queued and which the command removed and need queueing again.
+/// Tracks that have played since are left out, while tracks queued since keep their order
+/// after the restored ones.
+pub fn plan_restore(snapshot: &[UndoTrack], upcoming: &[&str]) -> Vec<RestoredTrack> {
+    let find_queued = |uuid: &str| upcoming.iter().position(|queued| *queued == uuid);
+
+    let restored =
+        snapshot
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, track)| match find_queued(&track.uuid) {
+                Some(position) => Some(RestoredTrack::Queued(position)),
+                None if track.removed => Some(RestoredTrack::Missing(idx)),
+                None => None,
+            });
+
+    let added = upcoming
+        .iter()
+        .enumerate()
+        .filter(|(_, uuid)| !snapshot.iter().any(|track| track.uuid == **uuid))
+        .map(|(position, _)| RestoredTrack::Queued(position));
+
+    restored.chain(added).collect()
+}
+
+/// Queues a track that's no longer in the queue again from its link, carrying its metadata
+/// and requester over to the new track. The metadata is already known, so nothing is
+/// looked up until the track comes up.
+async fn requeue_track(
+    ctx: &Context,
+    call: &Arc<Mutex<Call>>,
+    http_client: &reqwest::Client,
+    track: &UndoTrack,
+) -> Result<TrackHandle, ParrotError> {
+    let metadata = verify(
+        track.metadata.clone(),
+        ParrotError::Other("track has no metadata"),
+    )?;
+    let source_url = verify(
+        metadata.source_url.clone(),
+        ParrotError::Other("track has no source url"),
+    )?;
+
+    let input = get_replay_source(http_client.clone(), source_url);
+    let track_handle = call.lock().await.enqueue_input(input).await;
+    let uuid = track_handle.uuid().to_string();
+
+    let mut data = ctx.data.write().await;
+    let metadata_store = data.get_mut::<MetadataStore>().unwrap();
+    metadata_store.insert_metadata(uuid.clone(), metadata);
+
+    if let Some(requester) = track.requester {
+        metadata_store.insert_requester(uuid, requester);
+    }
+
+    Ok(track_handle)
+}
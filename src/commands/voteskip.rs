@@ -1,62 +1,232 @@
 use crate::{
-    commands::skip::{create_skip_response, force_skip_top_track},
+    commands::skip::{create_skip_response, force_skip_top_track, get_skip_message},
     connection::get_voice_channel_for_user,
     errors::{ParrotError, verify},
-    guild::cache::GuildCacheMap,
-    messaging::message::ParrotMessage,
-    utils::create_response,
+    guild::{
+        cache::GuildCacheMap,
+        metadata_store::MetadataStore,
+        settings::{DEFAULT_SKIP_THRESHOLD, GuildSettingsMap},
+    },
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_VOTE_INELIGIBLE, SKIP_VOTE_BUTTON, SKIP_VOTE_EXPIRED, SKIP_VOTE_TALLY},
+    },
 };
 use serenity::{
-    all::{CommandInteraction, GuildId},
+    all::{
+        ButtonStyle, CommandInteraction, CreateActionRow, CreateButton, CreateEmbed,
+        CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+        EditMessage, GuildId, UserId,
+    },
     client::Context,
+    futures::StreamExt,
     prelude::{Mentionable, RwLock, TypeMap},
 };
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+const VOTE_BUTTON_ID: &str = "voteskip";
+const VOTE_TIMEOUT: u64 = 300;
+
+enum Vote {
+    Passed,
+    Pending { votes: usize, required: usize },
+}
 
 pub async fn voteskip(
     ctx: &Context,
     interaction: &mut CommandInteraction,
 ) -> Result<(), ParrotError> {
     let guild_id = interaction.guild_id.unwrap();
-    let bot_channel_id = get_voice_channel_for_user(
-        &ctx.cache.guild(guild_id).unwrap(),
-        &ctx.cache.current_user().id,
-    )
-    .unwrap();
     let manager = songbird::get(ctx).await.unwrap();
     let call = manager.get(guild_id).unwrap();
 
     let handler = call.lock().await;
-    let queue = handler.queue();
+    let track = verify(handler.queue().current(), ParrotError::NothingPlaying)?;
 
-    verify(!queue.is_empty(), ParrotError::NothingPlaying)?;
+    // requesters don't need anyone's approval to skip their own track
+    let is_requester = {
+        let data = ctx.data.read().await;
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+        metadata_store.retrieve_requester(&track.uuid().to_string()) == Some(interaction.user.id)
+    };
 
-    let mut data = ctx.data.write().await;
-    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let vote = match is_requester {
+        true => Vote::Passed,
+        false => cast_vote(ctx, guild_id, interaction.user.id).await?,
+    };
 
-    let cache = cache_map.entry(guild_id).or_default();
-    cache.current_skip_votes.insert(interaction.user.id);
-
-    let guild_users = ctx.cache.guild(guild_id).unwrap().voice_states.clone();
-    let channel_guild_users = guild_users
-        .into_values()
-        .filter(|v| v.channel_id.unwrap() == bot_channel_id);
-    let skip_threshold = channel_guild_users.count() / 2;
-
-    if cache.current_skip_votes.len() >= skip_threshold {
-        force_skip_top_track(&handler).await?;
-        create_skip_response(ctx, interaction, &handler, 1).await
-    } else {
-        create_response(
+    let (votes, required) = match vote {
+        Vote::Passed => {
+            force_skip_top_track(&handler).await?;
+            return create_skip_response(ctx, interaction, &handler, 1).await;
+        }
+        Vote::Pending { votes, required } => (votes, required),
+    };
+    drop(handler);
+
+    interaction
+        .create_response(
             &ctx.http,
-            interaction,
-            ParrotMessage::VoteSkip {
-                mention: interaction.user.id.mention(),
-                missing: skip_threshold - cache.current_skip_votes.len(),
-            },
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .add_embed(create_vote_embed(interaction.user.id, votes, required))
+                    .components(vec![build_vote_btn()]),
+            ),
         )
-        .await
+        .await?;
+
+    let mut message = interaction.get_response(&ctx.http).await?;
+
+    let mut cib = message
+        .await_component_interactions(ctx)
+        .timeout(Duration::from_secs(VOTE_TIMEOUT))
+        .stream();
+
+    while let Some(mci) = cib.next().await {
+        if mci.data.custom_id != VOTE_BUTTON_ID {
+            continue;
+        }
+
+        let handler = call.lock().await;
+
+        // a vote only applies to the track it was started for
+        let is_same_track = handler
+            .queue()
+            .current()
+            .is_some_and(|current| current.uuid() == track.uuid());
+
+        if !is_same_track {
+            mci.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+                .await
+                .ok();
+            break;
+        }
+
+        let mut is_finished = false;
+
+        let response = match cast_vote(ctx, guild_id, mci.user.id).await {
+            Ok(Vote::Passed) => {
+                is_finished = true;
+                force_skip_top_track(&handler).await?;
+                let outcome = get_skip_message(ctx, &handler, 1).await?;
+
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .add_embed(CreateEmbed::new().description(format!("{outcome}")))
+                        .components(vec![]),
+                )
+            }
+            Ok(Vote::Pending { votes, required }) => CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new().add_embed(create_vote_embed(
+                    mci.user.id,
+                    votes,
+                    required,
+                )),
+            ),
+            Err(err) => CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .add_embed(CreateEmbed::new().description(format!("{err}")))
+                    .ephemeral(true),
+            ),
+        };
+        drop(handler);
+
+        mci.create_response(&ctx.http, response).await?;
+
+        if is_finished {
+            return Ok(());
+        }
     }
+
+    // the vote ran out of time or the track ended before it passed
+    message
+        .edit(
+            &ctx.http,
+            EditMessage::new()
+                .add_embed(CreateEmbed::new().description(SKIP_VOTE_EXPIRED))
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn cast_vote(ctx: &Context, guild_id: GuildId, user_id: UserId) -> Result<Vote, ParrotError> {
+    let voters = get_eligible_voters(ctx, guild_id);
+    verify(
+        voters.contains(&user_id),
+        ParrotError::Other(FAIL_VOTE_INELIGIBLE),
+    )?;
+
+    let mut data = ctx.data.write().await;
+    let threshold = data
+        .get::<GuildSettingsMap>()
+        .unwrap()
+        .get(&guild_id)
+        .map(|guild_settings| guild_settings.skip_threshold)
+        .unwrap_or(DEFAULT_SKIP_THRESHOLD);
+
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+    cache.current_skip_votes.insert(user_id);
+
+    // members who left or deafened since voting no longer count
+    let votes = cache.current_skip_votes.intersection(&voters).count();
+    let required = get_required_votes(voters.len(), threshold);
+
+    match votes >= required {
+        true => Ok(Vote::Passed),
+        false => Ok(Vote::Pending { votes, required }),
+    }
+}
+
+/// Everyone actually listening in the bot's channel, which leaves out bots and deafened members.
+fn get_eligible_voters(ctx: &Context, guild_id: GuildId) -> HashSet<UserId> {
+    let bot_id = ctx.cache.current_user().id;
+
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return HashSet::new();
+    };
+
+    let Some(bot_channel_id) = get_voice_channel_for_user(&guild, &bot_id) else {
+        return HashSet::new();
+    };
+
+    guild
+        .voice_states
+        .values()
+        .filter(|state| state.channel_id == Some(bot_channel_id))
+        .filter(|state| state.user_id != bot_id && !state.deaf && !state.self_deaf)
+        .filter(|state| !state.member.as_ref().is_some_and(|member| member.user.bot))
+        .map(|state| state.user_id)
+        .collect()
+}
+
+/// How many of the voters need to agree for the threshold, given as a percentage, to be met.
+pub fn get_required_votes(voters: usize, threshold: u8) -> usize {
+    (voters * threshold as usize).div_ceil(100).max(1)
+}
+
+fn create_vote_embed(voter: UserId, votes: usize, required: usize) -> CreateEmbed {
+    let message = ParrotMessage::VoteSkip {
+        mention: voter.mention(),
+        missing: required - votes,
+    };
+
+    CreateEmbed::new()
+        .description(format!("{message}"))
+        .footer(CreateEmbedFooter::new(format!(
+            "{}/{} {}",
+            votes, required, SKIP_VOTE_TALLY
+        )))
+}
+
+fn build_vote_btn() -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(VOTE_BUTTON_ID)
+            .label(SKIP_VOTE_BUTTON)
+            .style(ButtonStyle::Primary),
+    ])
 }
 
 pub async fn forget_skip_votes(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> Result<(), ()> {
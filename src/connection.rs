@@ -1,7 +1,19 @@
-use serenity::model::{
-    guild::Guild,
-    id::{ChannelId, UserId},
+use serenity::{
+    all::{ChannelType, CreateStageInstance, EditStageInstance, EditVoiceState, Permissions},
+    client::Context,
+    model::{
+        guild::Guild,
+        id::{ChannelId, GuildId, UserId},
+    },
+    prelude::{Mentionable, RwLock, TypeMap},
 };
+use songbird::Songbird;
+use std::sync::Arc;
+
+use crate::{errors::ParrotError, guild::cache::GuildCacheMap, utils::truncate_text};
+
+/// Discord's limit on the length of a stage topic.
+const STAGE_TOPIC_LIMIT: usize = 120;
 
 pub enum Connection {
     User(ChannelId),
@@ -30,9 +42,166 @@ pub fn check_voice_connections(guild: &Guild, user_id: &UserId, bot_id: &UserId)
     }
 }
 
+/// Checks whether a command can be used given where its author and Parrot are connected.
+pub fn check_command_connection(
+    command_name: &str,
+    connection: Connection,
+) -> Result<(), ParrotError> {
+    match command_name {
+        "autopause" | "clear" | "leave" | "loop" | "pause" | "remove" | "resume" | "seek"
+        | "shuffle" | "skip" | "sleep" | "stop" | "undo" | "voteskip" => {
+            check_shared_channel(connection)
+        }
+        "play" | "quiz" | "superplay" | "summon" => match connection {
+            Connection::User(_) => Ok(()),
+            Connection::Bot(_) if command_name == "summon" => Err(ParrotError::AuthorNotFound),
+            Connection::Bot(_) if command_name != "summon" => Err(ParrotError::WrongVoiceChannel),
+            Connection::Separate(bot_channel_id, _) => {
+                Err(ParrotError::AlreadyConnected(bot_channel_id.mention()))
+            }
+            Connection::Neither => Err(ParrotError::AuthorNotFound),
+            _ => Ok(()),
+        },
+        "like" | "np" | "queue" => match connection {
+            Connection::User(_) | Connection::Neither => Err(ParrotError::NotConnected),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Commands that change what's playing need the author listening along with Parrot.
+fn check_shared_channel(connection: Connection) -> Result<(), ParrotError> {
+    match connection {
+        Connection::User(_) | Connection::Neither => Err(ParrotError::NotConnected),
+        Connection::Bot(bot_channel_id) => {
+            Err(ParrotError::AuthorDisconnected(bot_channel_id.mention()))
+        }
+        Connection::Separate(_, _) => Err(ParrotError::WrongVoiceChannel),
+        _ => Ok(()),
+    }
+}
+
 pub fn get_voice_channel_for_user(guild: &Guild, user_id: &UserId) -> Option<ChannelId> {
     guild
         .voice_states
         .get(user_id)
         .and_then(|voice_state| voice_state.channel_id)
 }
+
+/// How many people, leaving out bots, are in the same voice channel as Parrot.
+/// Returns [`None`] if Parrot isn't in a voice channel.
+pub fn get_listener_count(ctx: &Context, guild_id: GuildId) -> Option<usize> {
+    let bot_id = ctx.cache.current_user().id;
+    let guild = ctx.cache.guild(guild_id)?;
+    let bot_channel_id = get_voice_channel_for_user(&guild, &bot_id)?;
+
+    let listeners = guild
+        .voice_states
+        .values()
+        .filter(|state| state.channel_id == Some(bot_channel_id) && state.user_id != bot_id)
+        .filter(|state| !state.member.as_ref().is_some_and(|member| member.user.bot))
+        .count();
+
+    Some(listeners)
+}
+
+/// Leaves the guild's voice channel along with every handler registered on the call.
+/// The disconnect is flagged as expected, so it isn't mistaken for a kick.
+pub async fn disconnect(
+    manager: &Arc<Songbird>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+) -> Result<(), ParrotError> {
+    let mut data = data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    cache_map.entry(guild_id).or_default().expected_disconnect = true;
+    drop(data);
+
+    if let Some(call) = manager.get(guild_id) {
+        let mut handler = call.lock().await;
+        handler.remove_all_global_events();
+    }
+
+    manager
+        .remove(guild_id)
+        .await
+        .map_err(|err| ParrotError::Join(Box::new(err)))
+}
+
+pub fn is_stage_channel(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> bool {
+    ctx.cache
+        .guild(guild_id)
+        .and_then(|guild| guild.channels.get(&channel_id).map(|channel| channel.kind))
+        .is_some_and(|kind| kind == ChannelType::Stage)
+}
+
+/// What Parrot is allowed to do in a stage channel.
+/// Returns [`None`] if the channel isn't a stage.
+pub async fn get_stage_permissions(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Option<Permissions> {
+    if !is_stage_channel(ctx, guild_id, channel_id) {
+        return None;
+    }
+
+    let bot_id = ctx.cache.current_user().id;
+    let Ok(member) = guild_id.member(ctx, bot_id).await else {
+        return Some(Permissions::empty());
+    };
+
+    let guild = ctx.cache.guild(guild_id)?;
+    let channel = guild.channels.get(&channel_id)?;
+    Some(guild.user_permissions_in(channel, &member))
+}
+
+/// Gets Parrot off the audience benches, either by taking the stage outright when it's
+/// allowed to, or by raising its hand and waiting for a moderator.
+pub async fn join_stage(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    permissions: Permissions,
+) -> Result<(), ParrotError> {
+    let builder = match permissions.mute_members() {
+        true => EditVoiceState::new().suppress(false),
+        false => EditVoiceState::new().request_to_speak(true),
+    };
+
+    let channel = ctx
+        .cache
+        .guild(guild_id)
+        .and_then(|guild| guild.channels.get(&channel_id).cloned())
+        .ok_or(ParrotError::NotConnected)?;
+
+    channel.edit_own_voice_state(&ctx.http, builder).await?;
+    Ok(())
+}
+
+/// Shortens a track title to something Discord accepts as a stage topic.
+pub fn get_stage_topic(title: &str) -> String {
+    truncate_text(title, STAGE_TOPIC_LIMIT)
+}
+
+/// Sets the topic of a stage, opening the stage first if nobody has yet.
+pub async fn update_stage_topic(
+    ctx: &Context,
+    channel_id: ChannelId,
+    title: &str,
+) -> Result<(), ParrotError> {
+    let topic = get_stage_topic(title);
+
+    let edited = channel_id
+        .edit_stage_instance(&ctx.http, EditStageInstance::new().topic(&topic))
+        .await;
+
+    if edited.is_err() {
+        channel_id
+            .create_stage_instance(&ctx.http, CreateStageInstance::new(topic))
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use crate::messaging::messages::{
-    FAIL_ANOTHER_CHANNEL, FAIL_AUTHOR_DISCONNECTED, FAIL_AUTHOR_NOT_FOUND,
-    FAIL_NO_VOICE_CONNECTION, FAIL_WRONG_CHANNEL, NOTHING_IS_PLAYING, QUEUE_IS_EMPTY,
-    TRACK_INAPPROPRIATE, TRACK_NOT_FOUND,
+use crate::{
+    guild::settings::PermissionLevel,
+    messaging::messages::{
+        FAIL_ANOTHER_CHANNEL, FAIL_AUTHOR_DISCONNECTED, FAIL_AUTHOR_NOT_FOUND,
+        FAIL_DUPLICATE_TRACK, FAIL_MISSING_ADMIN, FAIL_MISSING_DJ, FAIL_MISSING_STAGE,
+        FAIL_NO_VOICE_CONNECTION, FAIL_UNBOUND_CHANNEL, FAIL_WRONG_CHANNEL, NOTHING_IS_PLAYING,
+        QUEUE_IS_EMPTY, TRACK_INAPPROPRIATE, TRACK_NOT_FOUND,
+    },
 };
 use rspotify::ClientError as RSpotifyClientError;
 use serenity::{model::mention::Mention, prelude::SerenityError};
@@ -20,8 +24,12 @@ pub enum ParrotError {
     AuthorDisconnected(Mention),
     WrongVoiceChannel,
     AuthorNotFound,
+    MissingPermission(PermissionLevel),
+    MissingStagePermission,
     NothingPlaying,
+    DuplicateTrack(String),
     TrackFail(PlayError),
+    UnboundChannel(Vec<Mention>),
     AlreadyConnected(Mention),
     Serenity(Box<SerenityError>),
     RSpotify(RSpotifyClientError),
@@ -51,10 +59,18 @@ impl Display for ParrotError {
             }
             Self::WrongVoiceChannel => f.write_str(FAIL_WRONG_CHANNEL),
             Self::AuthorNotFound => f.write_str(FAIL_AUTHOR_NOT_FOUND),
+            Self::MissingPermission(level) => match level {
+                PermissionLevel::Admin => f.write_str(FAIL_MISSING_ADMIN),
+                _ => f.write_str(FAIL_MISSING_DJ),
+            },
+            Self::MissingStagePermission => f.write_str(FAIL_MISSING_STAGE),
             Self::AlreadyConnected(mention) => {
                 f.write_fmt(format_args!("{} {}", FAIL_ANOTHER_CHANNEL, mention))
             }
             Self::NothingPlaying => f.write_str(NOTHING_IS_PLAYING),
+            Self::DuplicateTrack(title) => {
+                f.write_fmt(format_args!("⚠️ **{}** {}", title, FAIL_DUPLICATE_TRACK))
+            }
             Self::TrackFail(err) => match err {
                 PlayError::Parse(error) => {
                     if error.to_string().contains("Sign in to confirm your age") {
@@ -65,6 +81,18 @@ impl Display for ParrotError {
                 }
                 _ => f.write_str(&format!("{err}")),
             },
+            Self::UnboundChannel(mentions) => {
+                let mentions = mentions
+                    .iter()
+                    .map(|mention| mention.to_string())
+                    .collect::<Vec<_>>();
+
+                f.write_fmt(format_args!(
+                    "{} {}",
+                    FAIL_UNBOUND_CHANNEL,
+                    mentions.join(", ")
+                ))
+            }
             Self::Serenity(err) => f.write_str(&format!("{err}")),
             Self::RSpotify(err) => f.write_str(&format!("{err}")),
             Self::IO(err) => f.write_str(&format!("{err}")),
@@ -90,6 +118,8 @@ impl PartialEq for ParrotError {
             (Self::AlreadyConnected(l0), Self::AlreadyConnected(r0)) => {
                 l0.to_string() == r0.to_string()
             }
+            (Self::MissingPermission(l0), Self::MissingPermission(r0)) => l0 == r0,
+            (Self::DuplicateTrack(l0), Self::DuplicateTrack(r0)) => l0 == r0,
             (Self::Serenity(l0), Self::Serenity(r0)) => format!("{l0:?}") == format!("{r0:?}"),
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
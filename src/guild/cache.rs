@@ -1,22 +1,74 @@
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::Instant,
 };
 
 use serenity::{
     model::{
         channel::Message,
-        id::{GuildId, UserId},
+        id::{ChannelId, GuildId, UserId},
     },
     prelude::{RwLock, TypeMapKey},
 };
+use songbird::input::AuxMetadata;
+use tokio::task::JoinHandle;
+
+use crate::commands::play::QueryType;
 
 type QueueMessage = (Message, Arc<RwLock<usize>>);
 
+/// A now playing message that keeps itself up to date until it expires.
+#[derive(Clone)]
+pub struct LiveMessage {
+    pub message: Message,
+    pub expires_at: Instant,
+    pub next_edit: Instant,
+}
+
+/// An upcoming track as it was before a destructive command, with everything needed to
+/// queue it again if it's gone by the time the command is undone.
+#[derive(Clone)]
+pub struct UndoTrack {
+    pub uuid: String,
+    pub metadata: Option<AuxMetadata>,
+    pub requester: Option<UserId>,
+    pub removed: bool,
+}
+
+/// What's waiting to put Parrot to sleep.
+pub enum SleepTimer {
+    /// Counting down, and aborted if the timer is cancelled.
+    Countdown(JoinHandle<()>),
+    /// Waiting for the track with this id to end.
+    AfterTrack(String),
+}
+
+/// The upcoming tracks as they were before a command reshaped the queue, so `/undo` can put them back.
+pub struct UndoSnapshot {
+    pub command: &'static str,
+    pub tracks: Vec<UndoTrack>,
+    pub stored_queue: Option<Vec<QueryType>>,
+    pub taken_at: Instant,
+}
+
 #[derive(Default)]
 pub struct GuildCache {
     pub queue_messages: Vec<QueueMessage>,
     pub current_skip_votes: HashSet<UserId>,
+    pub restarted_tracks: HashSet<String>,
+    pub alone_timer: Option<JoinHandle<()>>,
+    pub paused_while_alone: bool,
+    pub expected_disconnect: bool,
+    pub voice_channel: Option<ChannelId>,
+    pub text_channel: Option<ChannelId>,
+    pub now_playing_message: Option<Message>,
+    pub live_messages: Vec<LiveMessage>,
+    pub live_refresher: Option<JoinHandle<()>>,
+    pub undo_snapshot: Option<UndoSnapshot>,
+    pub sleep_timer: Option<SleepTimer>,
+    pub schedule_timers: HashMap<u32, JoinHandle<()>>,
+    pub quiz: Option<JoinHandle<()>>,
 }
 
 pub struct GuildCacheMap;
@@ -0,0 +1,104 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serenity::{model::id::UserId, prelude::TypeMapKey};
+use std::{
+    collections::HashMap,
+    env,
+    fs::{OpenOptions, create_dir_all},
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use crate::{
+    errors::{ParrotError, verify},
+    messaging::messages::{FAIL_ALREADY_LIKED, FAIL_FAVORITES_FULL},
+};
+
+const DEFAULT_FAVORITES_PATH: &str = "data/favorites";
+pub const MAX_FAVORITES: usize = 200;
+
+lazy_static! {
+    static ref FAVORITES_PATH: String =
+        env::var("FAVORITES_PATH").unwrap_or(DEFAULT_FAVORITES_PATH.to_string());
+}
+
+/// A track someone liked, with just enough to show it and queue it again.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Favorite {
+    pub title: String,
+    pub source_url: String,
+}
+
+/// Someone's favorites, which follow them from server to server.
+#[derive(Deserialize, Serialize)]
+pub struct UserFavorites {
+    pub user_id: UserId,
+    pub tracks: Vec<Favorite>,
+}
+
+impl UserFavorites {
+    pub fn new(user_id: UserId) -> UserFavorites {
+        UserFavorites {
+            user_id,
+            tracks: Vec::new(),
+        }
+    }
+
+    pub fn load_if_exists(&mut self) -> Result<(), ParrotError> {
+        let path = format!("{}/{}.json", FAVORITES_PATH.as_str(), self.user_id);
+        if !Path::new(&path).exists() {
+            return Ok(());
+        }
+        self.load()
+    }
+
+    pub fn load(&mut self) -> Result<(), ParrotError> {
+        let path = format!("{}/{}.json", FAVORITES_PATH.as_str(), self.user_id);
+        let file = OpenOptions::new().read(true).open(path)?;
+        let reader = BufReader::new(file);
+        *self = serde_json::from_reader::<_, UserFavorites>(reader)?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), ParrotError> {
+        create_dir_all(FAVORITES_PATH.as_str())?;
+        let path = format!("{}/{}.json", FAVORITES_PATH.as_str(), self.user_id);
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        let writer = BufWriter::new(file);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Adds a track to the end of the favorites, unless it's already there or they're full.
+    pub fn like(&mut self, favorite: Favorite) -> Result<(), ParrotError> {
+        let is_liked = self
+            .tracks
+            .iter()
+            .any(|track| track.source_url == favorite.source_url);
+
+        verify(!is_liked, ParrotError::Other(FAIL_ALREADY_LIKED))?;
+        verify(
+            self.tracks.len() < MAX_FAVORITES,
+            ParrotError::Other(FAIL_FAVORITES_FULL),
+        )?;
+
+        self.tracks.push(favorite);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Favorite> {
+        (index < self.tracks.len()).then(|| self.tracks.remove(index))
+    }
+}
+
+pub struct UserFavoritesMap;
+
+impl TypeMapKey for UserFavoritesMap {
+    type Value = HashMap<UserId, UserFavorites>;
+}
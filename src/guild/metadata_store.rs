@@ -1,17 +1,19 @@
 use std::collections::HashMap;
 
-use serenity::prelude::TypeMapKey;
+use serenity::{model::id::UserId, prelude::TypeMapKey};
 use songbird::input::AuxMetadata;
 
 #[derive(Debug, Clone, Default)]
 pub struct MetadataStore {
     store: HashMap<String, AuxMetadata>,
+    requesters: HashMap<String, UserId>,
 }
 
 impl MetadataStore {
     pub fn new() -> Self {
         Self {
             store: HashMap::new(),
+            requesters: HashMap::new(),
         }
     }
 
@@ -23,8 +25,17 @@ impl MetadataStore {
         self.store.insert(song_reference, metadata);
     }
 
+    pub fn retrieve_requester(&self, song_reference: &str) -> Option<UserId> {
+        self.requesters.get(song_reference).copied()
+    }
+
+    pub fn insert_requester(&mut self, song_reference: String, requester: UserId) {
+        self.requesters.insert(song_reference, requester);
+    }
+
     pub fn remove_metadata(&mut self, song_reference: &str) {
         self.store.remove(song_reference);
+        self.requesters.remove(song_reference);
     }
 }
 
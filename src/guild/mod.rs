@@ -1,5 +1,8 @@
 pub mod cache;
+pub mod favorites;
 pub mod http_client;
 pub mod metadata_store;
+pub mod play_log;
+pub mod schedule;
 pub mod settings;
 pub mod stored_queue;
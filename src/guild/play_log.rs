@@ -0,0 +1,228 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serenity::model::id::{GuildId, UserId};
+use std::{
+    collections::HashMap,
+    env,
+    fs::{OpenOptions, create_dir_all},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{errors::ParrotError, guild::schedule::SECONDS_PER_DAY};
+
+const DEFAULT_PLAY_LOG_PATH: &str = "data/play_log";
+
+/// Plays older than this are left out of stats and eventually dropped from the log.
+pub const PLAY_LOG_RETENTION: u64 = SECONDS_PER_DAY * 90;
+
+/// The most plays a guild's log keeps, however recent.
+pub const MAX_PLAY_LOG_ENTRIES: usize = 50_000;
+
+/// Once a log grows past this many bytes, it's pruned before it gets appended to again.
+const MAX_PLAY_LOG_SIZE: u64 = 16 * 1024 * 1024;
+
+/// How big a log may be after pruning, comfortably below [`MAX_PLAY_LOG_SIZE`] so a log
+/// full of long titles isn't rewritten again on every play that follows.
+pub const PRUNED_PLAY_LOG_SIZE: u64 = MAX_PLAY_LOG_SIZE / 4 * 3;
+
+/// Tracks skipped within moments of starting weren't really listened to.
+pub const MIN_LOGGED_PLAY_TIME: Duration = Duration::from_secs(30);
+
+/// How many tracks and requesters make it into the stats.
+pub const STATS_TOP_COUNT: usize = 5;
+
+lazy_static! {
+    static ref PLAY_LOG_PATH: String =
+        env::var("PLAY_LOG_PATH").unwrap_or(DEFAULT_PLAY_LOG_PATH.to_string());
+}
+
+/// A track that played in a guild, as written to its log once it ended.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PlayLogEntry {
+    pub title: String,
+    pub source_url: Option<String>,
+    pub requester: Option<UserId>,
+    /// When it ended, in seconds since the Unix epoch.
+    pub ended_at: u64,
+    /// How long it was listened to, in seconds.
+    pub play_time: u64,
+}
+
+impl PlayLogEntry {
+    /// When it started playing, in seconds since the Unix epoch.
+    pub fn started_at(&self) -> u64 {
+        self.ended_at.saturating_sub(self.play_time)
+    }
+}
+
+/// How often a track was played, told apart from others by its link when it has one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackPlays {
+    pub title: String,
+    pub source_url: Option<String>,
+    pub plays: usize,
+}
+
+/// A summary of a play log, or of the part of it a member requested.
+#[derive(Debug, PartialEq)]
+pub struct PlayStats {
+    pub plays: usize,
+    /// In seconds.
+    pub play_time: u64,
+    pub top_tracks: Vec<TrackPlays>,
+    pub top_requesters: Vec<(UserId, usize)>,
+    /// The hour of the day most plays started in, in UTC.
+    pub busiest_hour: Option<u64>,
+    /// The day of the week most plays started on, counting from Monday as 0.
+    pub busiest_weekday: Option<u64>,
+}
+
+impl PlayStats {
+    pub fn new(entries: &[PlayLogEntry]) -> PlayStats {
+        let mut tracks: HashMap<&str, TrackPlays> = HashMap::new();
+        let mut requesters: HashMap<UserId, usize> = HashMap::new();
+        let mut hours = [0usize; 24];
+        let mut weekdays = [0usize; 7];
+
+        for entry in entries {
+            let key = entry.source_url.as_deref().unwrap_or(&entry.title);
+            let track = tracks.entry(key).or_insert_with(|| TrackPlays {
+                title: entry.title.clone(),
+                source_url: entry.source_url.clone(),
+                plays: 0,
+            });
+            track.plays += 1;
+
+            if let Some(requester) = entry.requester {
+                *requesters.entry(requester).or_default() += 1;
+            }
+
+            let started_at = entry.started_at();
+            hours[((started_at % SECONDS_PER_DAY) / 3600) as usize] += 1;
+            // the epoch fell on a thursday
+            weekdays[((started_at / SECONDS_PER_DAY + 3) % 7) as usize] += 1;
+        }
+
+        let mut top_tracks: Vec<TrackPlays> = tracks.into_values().collect();
+        top_tracks.sort_by(|a, b| b.plays.cmp(&a.plays).then_with(|| a.title.cmp(&b.title)));
+        top_tracks.truncate(STATS_TOP_COUNT);
+
+        let mut top_requesters: Vec<(UserId, usize)> = requesters.into_iter().collect();
+        top_requesters.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_requesters.truncate(STATS_TOP_COUNT);
+
+        PlayStats {
+            plays: entries.len(),
+            play_time: entries.iter().map(|entry| entry.play_time).sum(),
+            top_tracks,
+            top_requesters,
+            busiest_hour: get_busiest(&hours),
+            busiest_weekday: get_busiest(&weekdays),
+        }
+    }
+}
+
+/// The earliest of the slots with the most plays, if anything was played at all.
+fn get_busiest(slots: &[usize]) -> Option<u64> {
+    let (idx, plays) = slots
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, plays)| **plays)?;
+
+    (*plays > 0).then_some(idx as u64)
+}
+
+/// Adds a play to the end of the guild's log.
+pub fn log_play(guild_id: GuildId, entry: &PlayLogEntry) -> Result<(), ParrotError> {
+    create_dir_all(PLAY_LOG_PATH.as_str())?;
+    let path = get_play_log_path(guild_id);
+
+    let is_oversized = Path::new(&path)
+        .metadata()
+        .is_ok_and(|metadata| metadata.len() > MAX_PLAY_LOG_SIZE);
+
+    if is_oversized {
+        prune_play_log(guild_id, entry.ended_at)?;
+    }
+
+    // one write per line, so plays ending at the same time don't get tangled up
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Reads the plays in the guild's log that are still within its retention, oldest first.
+/// Lines that can't be read are skipped rather than losing the rest of the log over them.
+pub fn load_play_log(guild_id: GuildId, now: u64) -> Result<Vec<PlayLogEntry>, ParrotError> {
+    let path = get_play_log_path(guild_id);
+    if !Path::new(&path).exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = OpenOptions::new().read(true).open(path)?;
+    let reader = BufReader::new(file);
+
+    let entries = reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<PlayLogEntry>(&line).ok())
+        .filter(|entry| entry.ended_at + PLAY_LOG_RETENTION > now)
+        .collect();
+
+    Ok(entries)
+}
+
+/// Rewrites the guild's log with only the plays it should still be keeping.
+pub fn prune_play_log(guild_id: GuildId, now: u64) -> Result<(), ParrotError> {
+    let path = get_play_log_path(guild_id);
+    if !Path::new(&path).exists() {
+        return Ok(());
+    }
+
+    let mut lines = load_play_log(guild_id, now)?
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<String>, _>>()?;
+    trim_play_log_lines(&mut lines, MAX_PLAY_LOG_ENTRIES, PRUNED_PLAY_LOG_SIZE);
+
+    let file = OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(path)?;
+
+    let mut writer = BufWriter::new(file);
+    for line in lines {
+        writer.write_all(line.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Drops the oldest lines of a log until at most `max_entries` are left,
+/// taking up no more than `max_size` bytes once written out one per line.
+pub fn trim_play_log_lines(lines: &mut Vec<String>, max_entries: usize, max_size: u64) {
+    let mut excess = lines.len().saturating_sub(max_entries);
+    let mut size: u64 = lines[excess..]
+        .iter()
+        .map(|line| line.len() as u64 + 1)
+        .sum();
+
+    while size > max_size {
+        size -= lines[excess].len() as u64 + 1;
+        excess += 1;
+    }
+
+    lines.drain(..excess);
+}
+
+fn get_play_log_path(guild_id: GuildId) -> String {
+    format!("{}/{}.jsonl", PLAY_LOG_PATH.as_str(), guild_id)
+}
@@ -0,0 +1,145 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serenity::{
+    model::id::{ChannelId, GuildId, UserId},
+    prelude::TypeMapKey,
+};
+use std::{
+    collections::HashMap,
+    env,
+    fs::{OpenOptions, create_dir_all},
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use crate::errors::ParrotError;
+
+const DEFAULT_SCHEDULES_PATH: &str = "data/schedules";
+pub const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+pub const SECONDS_PER_WEEK: u64 = SECONDS_PER_DAY * 7;
+
+lazy_static! {
+    static ref SCHEDULES_PATH: String =
+        env::var("SCHEDULES_PATH").unwrap_or(DEFAULT_SCHEDULES_PATH.to_string());
+}
+
+/// A playlist or station Parrot starts playing in a voice channel on its own.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Schedule {
+    pub id: u32,
+    pub channel_id: ChannelId,
+    pub query: String,
+    /// When it's due next, in seconds since the Unix epoch.
+    pub next_run: u64,
+    pub weekly: bool,
+    pub created_by: UserId,
+}
+
+impl Schedule {
+    /// Moves a weekly schedule on to its first run after `now`, skipping any it missed.
+    /// Returns whether the schedule is still due at all.
+    pub fn advance(&mut self, now: u64) -> bool {
+        if self.next_run > now {
+            return true;
+        }
+
+        if !self.weekly {
+            return false;
+        }
+
+        let missed = (now - self.next_run) / SECONDS_PER_WEEK + 1;
+        self.next_run += missed * SECONDS_PER_WEEK;
+        true
+    }
+}
+
+/// A guild's schedules, which are kept in a file of their own next to its settings.
+#[derive(Deserialize, Serialize)]
+pub struct GuildSchedules {
+    pub guild_id: GuildId,
+    pub schedules: Vec<Schedule>,
+    next_id: u32,
+}
+
+impl GuildSchedules {
+    pub fn new(guild_id: GuildId) -> GuildSchedules {
+        GuildSchedules {
+            guild_id,
+            schedules: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn load_if_exists(&mut self) -> Result<(), ParrotError> {
+        let path = format!("{}/{}.json", SCHEDULES_PATH.as_str(), self.guild_id);
+        if !Path::new(&path).exists() {
+            return Ok(());
+        }
+        self.load()
+    }
+
+    pub fn load(&mut self) -> Result<(), ParrotError> {
+        let path = format!("{}/{}.json", SCHEDULES_PATH.as_str(), self.guild_id);
+        let file = OpenOptions::new().read(true).open(path)?;
+        let reader = BufReader::new(file);
+        *self = serde_json::from_reader::<_, GuildSchedules>(reader)?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), ParrotError> {
+        create_dir_all(SCHEDULES_PATH.as_str())?;
+        let path = format!("{}/{}.json", SCHEDULES_PATH.as_str(), self.guild_id);
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        let writer = BufWriter::new(file);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Adds a schedule, handing out an id that hasn't been used in this guild before.
+    pub fn add(
+        &mut self,
+        channel_id: ChannelId,
+        query: String,
+        next_run: u64,
+        weekly: bool,
+        created_by: UserId,
+    ) -> Schedule {
+        let schedule = Schedule {
+            id: self.next_id,
+            channel_id,
+            query,
+            next_run,
+            weekly,
+            created_by,
+        };
+
+        self.next_id += 1;
+        self.schedules.push(schedule.clone());
+        schedule
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Schedule> {
+        self.schedules.iter().find(|schedule| schedule.id == id)
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<Schedule> {
+        let position = self
+            .schedules
+            .iter()
+            .position(|schedule| schedule.id == id)?;
+
+        Some(self.schedules.remove(position))
+    }
+}
+
+pub struct GuildSchedulesMap;
+
+impl TypeMapKey for GuildSchedulesMap {
+    type Value = HashMap<GuildId, GuildSchedules>;
+}
@@ -1,31 +1,214 @@
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use serenity::{model::id::GuildId, prelude::TypeMapKey};
+use serenity::{
+    model::id::{ChannelId, GuildId, RoleId},
+    prelude::TypeMapKey,
+};
 use std::{
     collections::{HashMap, HashSet},
     env,
+    fmt::{self, Display},
     fs::{OpenOptions, create_dir_all},
     io::{BufReader, BufWriter},
     path::Path,
+    str::FromStr,
 };
 
-use crate::errors::ParrotError;
+use crate::{errors::ParrotError, permissions::default_permission_level, utils::compare_domains};
 
 const DEFAULT_SETTINGS_PATH: &str = "data/settings";
 const DEFAULT_ALLOWED_DOMAINS: [&str; 1] = ["youtube.com"];
+/// Where [`TrackFallback::AlternateSource`] searches.
+pub const ALTERNATE_SOURCE_DOMAIN: &str = "soundcloud.com";
+pub const DEFAULT_SKIP_THRESHOLD: u8 = 50;
+pub const DEFAULT_IDLE_TIMEOUT: u64 = 60 * 10;
+pub const DEFAULT_ALONE_TIMEOUT: u64 = 60;
 
 lazy_static! {
     static ref SETTINGS_PATH: String =
         env::var("SETTINGS_PATH").unwrap_or(DEFAULT_SETTINGS_PATH.to_string());
 }
 
+/// What to try when a track can't be loaded.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub enum TrackFallback {
+    Off,
+    Requery,
+    AlternateSource,
+    #[default]
+    Both,
+}
+
+impl FromStr for TrackFallback {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "requery" => Ok(Self::Requery),
+            "alternate" => Ok(Self::AlternateSource),
+            "both" => Ok(Self::Both),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for TrackFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => f.write_str("off"),
+            Self::Requery => f.write_str("retry with a simpler search"),
+            Self::AlternateSource => f.write_str("search on SoundCloud"),
+            Self::Both => f.write_str("retry with a simpler search, then search on SoundCloud"),
+        }
+    }
+}
+
+/// What gets played again once it's over.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub enum LoopMode {
+    #[default]
+    Off,
+    Track,
+    Queue,
+}
+
+impl LoopMode {
+    /// The mode after this one, for cycling through them with a single button.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Track,
+            Self::Track => Self::Queue,
+            Self::Queue => Self::Off,
+        }
+    }
+}
+
+impl FromStr for LoopMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "track" => Ok(Self::Track),
+            "queue" => Ok(Self::Queue),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What to do when someone asks for a track that's already in the queue.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub enum DuplicatePolicy {
+    Allow,
+    #[default]
+    Warn,
+    Refuse,
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "refuse" => Ok(Self::Refuse),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for DuplicatePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => f.write_str("queue it anyway"),
+            Self::Warn => f.write_str("queue it, but let you know"),
+            Self::Refuse => f.write_str("refuse to queue it"),
+        }
+    }
+}
+
+/// Who may run a command, from least to most privileged.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PermissionLevel {
+    Everyone,
+    Dj,
+    Admin,
+}
+
+impl FromStr for PermissionLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "everyone" => Ok(Self::Everyone),
+            "dj" => Ok(Self::Dj),
+            "admin" => Ok(Self::Admin),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for PermissionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Everyone => f.write_str("everyone"),
+            Self::Dj => f.write_str("DJs"),
+            Self::Admin => f.write_str("admins"),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct GuildSettings {
     pub guild_id: GuildId,
     pub autopause: bool,
     pub allowed_domains: HashSet<String>,
     pub banned_domains: HashSet<String>,
-    pub queue_loop: bool,
+    #[serde(default)]
+    pub loop_mode: LoopMode,
+    /// Only read from settings saved before there were loop modes, see [`LoopMode`].
+    #[serde(default, skip_serializing)]
+    queue_loop: bool,
+    #[serde(default)]
+    pub track_fallback: TrackFallback,
+    #[serde(default)]
+    pub dj_role: Option<RoleId>,
+    #[serde(default)]
+    pub command_permissions: HashMap<String, PermissionLevel>,
+    #[serde(default)]
+    pub bound_channels: HashSet<ChannelId>,
+    #[serde(default = "default_skip_threshold")]
+    pub skip_threshold: u8,
+    #[serde(default = "default_idle_timeout")]
+    pub idle_timeout: u64,
+    #[serde(default = "default_alone_timeout")]
+    pub alone_timeout: u64,
+    #[serde(default)]
+    pub always_on: bool,
+    #[serde(default)]
+    pub home_channel: Option<ChannelId>,
+    #[serde(default)]
+    pub fallback_playlist: Option<String>,
+    #[serde(default)]
+    pub stage_topic: bool,
+    #[serde(default)]
+    pub announce_channel: Option<ChannelId>,
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+}
+
+fn default_skip_threshold() -> u8 {
+    DEFAULT_SKIP_THRESHOLD
+}
+
+fn default_idle_timeout() -> u64 {
+    DEFAULT_IDLE_TIMEOUT
+}
+
+fn default_alone_timeout() -> u64 {
+    DEFAULT_ALONE_TIMEOUT
 }
 
 impl GuildSettings {
@@ -38,9 +221,23 @@ impl GuildSettings {
         GuildSettings {
             guild_id,
             autopause: false,
+            loop_mode: LoopMode::default(),
             queue_loop: false,
             allowed_domains,
             banned_domains: HashSet::new(),
+            track_fallback: TrackFallback::default(),
+            dj_role: None,
+            command_permissions: HashMap::new(),
+            bound_channels: HashSet::new(),
+            skip_threshold: DEFAULT_SKIP_THRESHOLD,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            alone_timeout: DEFAULT_ALONE_TIMEOUT,
+            always_on: false,
+            home_channel: None,
+            fallback_playlist: None,
+            stage_topic: false,
+            announce_channel: None,
+            duplicate_policy: DuplicatePolicy::default(),
         }
     }
 
@@ -57,6 +254,11 @@ impl GuildSettings {
         let file = OpenOptions::new().read(true).open(path)?;
         let reader = BufReader::new(file);
         *self = serde_json::from_reader::<_, GuildSettings>(reader)?;
+
+        if std::mem::take(&mut self.queue_loop) {
+            self.loop_mode = LoopMode::Queue;
+        }
+
         Ok(())
     }
 
@@ -79,10 +281,6 @@ impl GuildSettings {
         self.autopause = !self.autopause;
     }
 
-    pub fn toggle_queue_loop(&mut self) {
-        self.queue_loop = !self.queue_loop;
-    }
-
     pub fn set_allowed_domains(&mut self, allowed_str: &str) {
         let allowed = allowed_str
             .split(';')
@@ -103,6 +301,49 @@ impl GuildSettings {
         self.banned_domains = banned;
     }
 
+    /// Whether tracks may be streamed from the given domain, according to the allowed and banned lists.
+    pub fn is_domain_allowed(&self, domain: &str) -> bool {
+        let is_allowed = self
+            .allowed_domains
+            .iter()
+            .any(|d| compare_domains(d, domain));
+
+        let is_banned = self
+            .banned_domains
+            .iter()
+            .any(|d| compare_domains(d, domain));
+
+        !is_banned && (!self.banned_domains.is_empty() || is_allowed)
+    }
+
+    /// The fallback failed tracks actually get, which leaves out searching elsewhere
+    /// unless streaming from there is allowed, as it would be for any other query.
+    pub fn get_track_fallback(&self) -> TrackFallback {
+        if self.is_domain_allowed(ALTERNATE_SOURCE_DOMAIN) {
+            return self.track_fallback;
+        }
+
+        match self.track_fallback {
+            TrackFallback::AlternateSource => TrackFallback::Off,
+            TrackFallback::Both => TrackFallback::Requery,
+            fallback => fallback,
+        }
+    }
+
+    /// The level required to run a command, which is the default one unless it was overridden.
+    pub fn get_permission_level(&self, command: &str) -> PermissionLevel {
+        self.command_permissions
+            .get(command)
+            .copied()
+            .or_else(|| default_permission_level(command))
+            .unwrap_or(PermissionLevel::Everyone)
+    }
+
+    /// Whether commands may be used in the given text channel, which is any channel if none were bound.
+    pub fn is_channel_bound(&self, channel_id: ChannelId) -> bool {
+        self.bound_channels.is_empty() || self.bound_channels.contains(&channel_id)
+    }
+
     pub fn update_domains(&mut self) {
         if !self.allowed_domains.is_empty() && !self.banned_domains.is_empty() {
             self.banned_domains.clear();
@@ -17,6 +17,17 @@ impl GuildStoredQueue {
             continue_play: true,
         }
     }
+
+    /// Replaces the stored queries with the links of the tracks that are actually queued,
+    /// since searches and playlists can't otherwise be traced back to the tracks they found.
+    /// Tracks without a link couldn't be queued again anyway, so they're left out.
+    pub fn sync(&mut self, source_urls: &[Option<&str>]) {
+        self.queue = source_urls
+            .iter()
+            .flatten()
+            .map(|source_url| QueryType::from_source_url(source_url.to_string()))
+            .collect();
+    }
 }
 
 pub struct GuildStoredQueueMap;
@@ -0,0 +1,124 @@
+use serenity::{
+    client::Context,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use songbird::{Call, tracks::PlayMode};
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    connection::{disconnect, get_listener_count, get_voice_channel_for_user},
+    guild::{
+        cache::GuildCacheMap,
+        settings::{DEFAULT_ALONE_TIMEOUT, GuildSettingsMap},
+    },
+    messaging::messages::ALONE_ALERT,
+};
+
+/// Pauses once everyone else has left Parrot's voice channel and leaves if nobody comes back
+/// in time, resuming playback if somebody does.
+pub async fn update_alone_timer(ctx: &Context, guild_id: GuildId) {
+    let manager = songbird::get(ctx).await.unwrap();
+    let Some(call) = manager.get(guild_id) else {
+        return;
+    };
+
+    let Some(listeners) = get_listener_count(ctx, guild_id) else {
+        return;
+    };
+
+    // the call is locked first and held throughout, so pausing and remembering that
+    // it was paused happen together, before anyone rejoining can look at the flag
+    let handler = call.lock().await;
+    let mut data = ctx.data.write().await;
+    let guild_settings = data.get::<GuildSettingsMap>().unwrap().get(&guild_id);
+
+    // in 24/7 mode the music keeps going for whoever drops by next
+    if guild_settings.is_some_and(|guild_settings| guild_settings.always_on) {
+        return;
+    }
+
+    let grace_period = guild_settings
+        .map(|guild_settings| guild_settings.alone_timeout)
+        .unwrap_or(DEFAULT_ALONE_TIMEOUT);
+
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+
+    if listeners == 0 {
+        if cache.alone_timer.is_some() {
+            return;
+        }
+
+        cache.alone_timer = Some(tokio::spawn(leave_when_alone(
+            ctx.clone(),
+            guild_id,
+            Duration::from_secs(grace_period),
+        )));
+        cache.paused_while_alone = pause_if_playing(&handler).await;
+    } else {
+        let Some(timer) = cache.alone_timer.take() else {
+            return;
+        };
+
+        timer.abort();
+        if std::mem::take(&mut cache.paused_while_alone) {
+            handler.queue().resume().ok();
+        }
+    }
+}
+
+/// Stops a pending leave, e.g. because Parrot was disconnected some other way.
+pub async fn cancel_alone_timer(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) {
+    let mut data = data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+
+    if let Some(cache) = cache_map.get_mut(&guild_id) {
+        if let Some(timer) = cache.alone_timer.take() {
+            timer.abort();
+        }
+        cache.paused_while_alone = false;
+    }
+}
+
+async fn leave_when_alone(ctx: Context, guild_id: GuildId, grace_period: Duration) {
+    tokio::time::sleep(grace_period).await;
+
+    // the timer is done either way, so don't let anyone abort it from here on
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+    cache.alone_timer = None;
+    cache.paused_while_alone = false;
+    drop(data);
+
+    let bot_channel_id = {
+        let bot_id = ctx.cache.current_user().id;
+        ctx.cache
+            .guild(guild_id)
+            .and_then(|guild| get_voice_channel_for_user(&guild, &bot_id))
+    };
+
+    let manager = songbird::get(&ctx).await.unwrap();
+    if disconnect(&manager, &ctx.data, guild_id).await.is_err() {
+        return;
+    }
+
+    // voice channels have their own text chat, which is where the listeners were
+    if let Some(channel_id) = bot_channel_id {
+        channel_id.say(&ctx.http, ALONE_ALERT).await.ok();
+    }
+}
+
+async fn pause_if_playing(handler: &Call) -> bool {
+    let Some(track) = handler.queue().current() else {
+        return false;
+    };
+
+    let is_playing = track
+        .get_info()
+        .await
+        .is_ok_and(|state| matches!(state.playing, PlayMode::Play));
+
+    is_playing && handler.queue().pause().is_ok()
+}
@@ -0,0 +1,241 @@
+use serenity::{
+    all::{ChannelId, GuildId, UserId},
+    async_trait,
+    client::Context,
+};
+use songbird::{
+    Event, EventContext, EventHandler,
+    events::context_data::{DisconnectKind, DisconnectReason},
+    model::CloseCode,
+};
+use std::time::Duration;
+
+use crate::{
+    commands::{
+        play::{Mode, QueryType, normal_query_type_resolver},
+        summon::join_channel,
+    },
+    connection::disconnect,
+    errors::ParrotError,
+    guild::{
+        cache::GuildCacheMap, metadata_store::MetadataStore, settings::GuildSettingsMap,
+        stored_queue::GuildStoredQueueMap,
+    },
+    handlers::track_end::update_queue_messages,
+    messaging::messages::RECONNECT_FAILED,
+};
+
+const RECONNECT_ATTEMPTS: u32 = 3;
+const RECONNECT_DELAY: u64 = 5;
+
+/// Reconnects to the same channel after the voice connection drops on its own, as opposed
+/// to Parrot being kicked or asked to leave, and picks playback back up where it was.
+#[derive(Clone)]
+pub struct DriverDisconnectHandler {
+    pub ctx: Context,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+}
+
+/// What was queued when the connection dropped, so it can be queued again.
+struct QueueSnapshot {
+    tracks: Vec<(QueryType, Option<UserId>)>,
+    position: Duration,
+}
+
+#[async_trait]
+impl EventHandler for DriverDisconnectHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::DriverDisconnect(disconnect_data) = ctx else {
+            return None;
+        };
+
+        // failing to connect in the first place is reported to whoever asked for it
+        if !matches!(disconnect_data.kind, DisconnectKind::Runtime) {
+            return None;
+        }
+
+        // no reason means parrot left on purpose, and 4014 means it was kicked or the
+        // channel went away, neither of which should be undone
+        let is_kicked = matches!(
+            disconnect_data.reason,
+            Some(DisconnectReason::WsClosed(Some(CloseCode::Disconnected)))
+        );
+        let is_transient = disconnect_data.reason.is_some() && !is_kicked;
+
+        let expected_disconnect = {
+            let data = self.ctx.data.read().await;
+            data.get::<GuildCacheMap>()?
+                .get(&self.guild_id)
+                .is_some_and(|cache| cache.expected_disconnect)
+        };
+
+        if expected_disconnect {
+            return None;
+        }
+
+        // the voice state update leaves the call alone unless parrot meant to leave,
+        // so being kicked is cleaned up from here
+        if is_kicked {
+            let handler = self.clone();
+            tokio::spawn(async move { handler.forget_call().await });
+            return None;
+        }
+
+        if !is_transient {
+            return None;
+        }
+
+        let voice_channel_id: ChannelId = disconnect_data.channel_id?.0.into();
+        println!(
+            "[WARN] Lost the voice connection in guild {} due to {:?}, reconnecting",
+            self.guild_id, disconnect_data.reason
+        );
+
+        // taken right away, while the call and its queue are sure to still be around
+        let snapshot = self.take_snapshot().await;
+
+        // events are handled one at a time, so don't hold the others up while retrying
+        let handler = self.clone();
+        tokio::spawn(async move { handler.recover(voice_channel_id, snapshot).await });
+
+        None
+    }
+}
+
+impl DriverDisconnectHandler {
+    async fn recover(&self, voice_channel_id: ChannelId, snapshot: QueueSnapshot) {
+        for attempt in 1..=RECONNECT_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY * attempt as u64)).await;
+
+            match self.reconnect(voice_channel_id, &snapshot).await {
+                Ok(_) => return,
+                Err(err) => println!("[ERROR] Reconnection attempt {} failed: {}", attempt, err),
+            }
+        }
+
+        self.channel_id
+            .say(&self.ctx.http, RECONNECT_FAILED)
+            .await
+            .ok();
+    }
+
+    /// Leaves for good after being kicked, unless 24/7 mode is about to rejoin the home channel.
+    async fn forget_call(&self) {
+        let always_on = {
+            let data = self.ctx.data.read().await;
+            data.get::<GuildSettingsMap>()
+                .unwrap()
+                .get(&self.guild_id)
+                .is_some_and(|guild_settings| guild_settings.always_on)
+        };
+
+        if always_on {
+            return;
+        }
+
+        let manager = songbird::get(&self.ctx).await.unwrap();
+        disconnect(&manager, &self.ctx.data, self.guild_id)
+            .await
+            .ok();
+        update_queue_messages(&self.ctx.http, &self.ctx.data, &[], self.guild_id).await;
+    }
+
+    async fn take_snapshot(&self) -> QueueSnapshot {
+        let manager = songbird::get(&self.ctx).await.unwrap();
+
+        let tracks = match manager.get(self.guild_id) {
+            Some(call) => call.lock().await.queue().current_queue(),
+            None => Vec::new(),
+        };
+
+        let position = match tracks.first() {
+            Some(track) => track
+                .get_info()
+                .await
+                .map(|state| state.position)
+                .unwrap_or_default(),
+            None => Duration::ZERO,
+        };
+
+        let data = self.ctx.data.read().await;
+        let metadata_store = data.get::<MetadataStore>().unwrap();
+
+        let tracks = tracks
+            .iter()
+            .filter_map(|track| {
+                let uuid = track.uuid().to_string();
+                let source_url = metadata_store
+                    .retrieve_metadata(&uuid)?
+                    .source_url
+                    .clone()?;
+
+                Some((
+                    QueryType::from_source_url(source_url),
+                    metadata_store.retrieve_requester(&uuid),
+                ))
+            })
+            .collect();
+
+        QueueSnapshot { tracks, position }
+    }
+
+    async fn reconnect(
+        &self,
+        voice_channel_id: ChannelId,
+        snapshot: &QueueSnapshot,
+    ) -> Result<(), ParrotError> {
+        let call =
+            join_channel(&self.ctx, self.guild_id, voice_channel_id, self.channel_id).await?;
+
+        // songbird might have held on to the tracks, in which case there's nothing to rebuild
+        let is_queue_empty = call.lock().await.queue().is_empty();
+        if !is_queue_empty {
+            return Ok(());
+        }
+
+        // without metadata to go on, fall back to what was originally requested
+        let tracks = match snapshot.tracks.is_empty() {
+            true => {
+                let data = self.ctx.data.read().await;
+                data.get::<GuildStoredQueueMap>()
+                    .unwrap()
+                    .get(&self.guild_id)
+                    .map(|guild_stored_queue| {
+                        guild_stored_queue
+                            .queue
+                            .iter()
+                            .map(|query_type| (query_type.clone(), None))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            false => snapshot.tracks.clone(),
+        };
+
+        for (query_type, requester) in tracks.iter() {
+            if let Err(err) = normal_query_type_resolver(
+                &call,
+                &self.ctx.http,
+                &self.ctx.data,
+                self.guild_id,
+                query_type,
+                Mode::End,
+                *requester,
+            )
+            .await
+            {
+                println!("[ERROR] Failed to requeue {:?} due to {}", query_type, err);
+            }
+        }
+
+        let current_track = call.lock().await.queue().current();
+        if let Some(track) = current_track
+            && !snapshot.tracks.is_empty()
+        {
+            let _ = track.seek(snapshot.position);
+        }
+
+        Ok(())
+    }
+}
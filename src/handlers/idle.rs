@@ -1,17 +1,27 @@
-use serenity::{all::CommandInteraction, async_trait, http::Http};
+use serenity::{
+    all::{ChannelId, GuildId},
+    async_trait,
+    http::Http,
+    prelude::{RwLock, TypeMap},
+};
 use songbird::{Event, EventContext, EventHandler, Songbird, tracks::PlayMode};
 use std::sync::{
     Arc,
     atomic::{AtomicUsize, Ordering},
 };
 
-use crate::messaging::messages::IDLE_ALERT;
+use crate::{
+    connection::disconnect,
+    guild::settings::{DEFAULT_IDLE_TIMEOUT, GuildSettingsMap},
+    messaging::messages::IDLE_ALERT,
+};
 
 pub struct IdleHandler {
     pub http: Arc<Http>,
     pub manager: Arc<Songbird>,
-    pub interaction: CommandInteraction,
-    pub limit: usize,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub ctx_data: Arc<RwLock<TypeMap>>,
     pub count: Arc<AtomicUsize>,
 }
 
@@ -34,23 +44,29 @@ impl EventHandler for IdleHandler {
             return None;
         }
 
-        if self.count.fetch_add(1, Ordering::Relaxed) >= self.limit {
-            let guild_id = self.interaction.guild_id?;
-            let call = self.manager.get(guild_id).unwrap();
+        // read on every tick so a changed setting applies right away
+        let (limit, always_on) = {
+            let data = self.ctx_data.read().await;
+            data.get::<GuildSettingsMap>()?
+                .get(&self.guild_id)
+                .map(|guild_settings| (guild_settings.idle_timeout, guild_settings.always_on))
+                .unwrap_or((DEFAULT_IDLE_TIMEOUT, false))
+        };
 
-            let mut handler = call.lock().await;
-            handler.remove_all_global_events();
-            drop(handler);
+        // 24/7 mode stays connected no matter how long it's been quiet
+        if always_on {
+            self.count.store(0, Ordering::Relaxed);
+            return None;
+        }
 
-            if self.manager.remove(guild_id).await.is_ok() {
-                self.count.store(0, Ordering::Relaxed);
+        if self.count.fetch_add(1, Ordering::Relaxed) as u64 >= limit
+            && disconnect(&self.manager, &self.ctx_data, self.guild_id)
+                .await
+                .is_ok()
+        {
+            self.count.store(0, Ordering::Relaxed);
 
-                self.interaction
-                    .channel_id
-                    .say(&self.http, IDLE_ALERT)
-                    .await
-                    .unwrap();
-            }
+            self.channel_id.say(&self.http, IDLE_ALERT).await.unwrap();
         }
 
         None
@@ -0,0 +1,153 @@
+use serenity::{
+    Error,
+    all::{EditMessage, Message, MessageId},
+    client::Context,
+    http::HttpError,
+    model::id::GuildId,
+};
+use songbird::tracks::PlayMode;
+use std::time::{Duration, Instant};
+
+use crate::{
+    guild::cache::{GuildCacheMap, LiveMessage},
+    utils::create_now_playing_embed,
+};
+
+const REFRESH_INTERVAL: u64 = 10;
+const RATE_LIMITED_INTERVAL: u64 = 30;
+const LIVE_MESSAGE_EXPIRY: u64 = 900;
+
+enum EditOutcome {
+    Edited,
+    RateLimited,
+    Gone,
+}
+
+/// Keeps a now playing message up to date for a while, starting the guild's refresher
+/// if it isn't running already. Tracking a message again extends its life.
+pub async fn track_live_message(ctx: &Context, guild_id: GuildId, message: Message) {
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+
+    let now = Instant::now();
+    cache
+        .live_messages
+        .retain(|live_message| live_message.message.id != message.id);
+
+    cache.live_messages.push(LiveMessage {
+        message,
+        expires_at: now + Duration::from_secs(LIVE_MESSAGE_EXPIRY),
+        next_edit: now + Duration::from_secs(REFRESH_INTERVAL),
+    });
+
+    if cache.live_refresher.is_none() {
+        cache.live_refresher = Some(tokio::spawn(refresh_live_messages(ctx.clone(), guild_id)));
+    }
+}
+
+/// Edits the guild's live messages on a schedule until there are none left to edit.
+/// Each message has its own schedule, which backs off whenever Discord pushes back.
+async fn refresh_live_messages(ctx: Context, guild_id: GuildId) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(REFRESH_INTERVAL)).await;
+
+        let manager = songbird::get(&ctx).await.unwrap();
+        let track = match manager.get(guild_id) {
+            Some(call) => call.lock().await.queue().current(),
+            None => None,
+        };
+
+        let now = Instant::now();
+        let due = {
+            let mut data = ctx.data.write().await;
+            let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+            let cache = cache_map.entry(guild_id).or_default();
+
+            // with nothing playing there's nothing left to keep up with
+            if track.is_none() {
+                cache.live_messages.clear();
+            }
+
+            cache
+                .live_messages
+                .retain(|live_message| live_message.expires_at > now);
+
+            if cache.live_messages.is_empty() {
+                cache.live_refresher = None;
+                return;
+            }
+
+            cache
+                .live_messages
+                .iter()
+                .filter(|live_message| live_message.next_edit <= now)
+                .map(|live_message| live_message.message.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let Some(track) = track else {
+            continue;
+        };
+
+        // a paused track stays put, so the messages already show the right thing
+        let is_playing = track
+            .get_info()
+            .await
+            .is_ok_and(|state| matches!(state.playing, PlayMode::Play));
+
+        if !is_playing || due.is_empty() {
+            continue;
+        }
+
+        let Ok(embed) = create_now_playing_embed(&track, &ctx.data).await else {
+            continue;
+        };
+
+        let mut outcomes: Vec<(MessageId, EditOutcome)> = Vec::new();
+        for mut message in due {
+            let result = message
+                .edit(&ctx.http, EditMessage::new().embed(embed.clone()))
+                .await;
+
+            let outcome = match result {
+                Ok(_) => EditOutcome::Edited,
+                Err(Error::Http(HttpError::UnsuccessfulRequest(ref response)))
+                    if response.status_code.as_u16() == 429 =>
+                {
+                    EditOutcome::RateLimited
+                }
+                Err(_) => EditOutcome::Gone,
+            };
+
+            outcomes.push((message.id, outcome));
+        }
+
+        let mut data = ctx.data.write().await;
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let cache = cache_map.entry(guild_id).or_default();
+
+        for (message_id, outcome) in outcomes {
+            let interval = match outcome {
+                EditOutcome::Edited => REFRESH_INTERVAL,
+                EditOutcome::RateLimited => RATE_LIMITED_INTERVAL,
+                EditOutcome::Gone => {
+                    // most likely deleted, so there's nothing left to edit
+                    cache
+                        .live_messages
+                        .retain(|live_message| live_message.message.id != message_id);
+                    continue;
+                }
+            };
+
+            let live_message = cache
+                .live_messages
+                .iter_mut()
+                .find(|live_message| live_message.message.id == message_id);
+
+            if let Some(live_message) = live_message {
+                live_message.next_edit = now + Duration::from_secs(interval);
+            }
+        }
+    }
+}
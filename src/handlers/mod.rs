@@ -1,7 +1,17 @@
+pub mod alone;
+pub mod disconnect;
 pub mod idle;
+pub mod live_message;
+pub mod schedule;
 pub mod serenity;
+pub mod sleep;
 pub mod track_end;
+pub mod track_error;
+pub mod track_start;
 
+pub use self::disconnect::DriverDisconnectHandler;
 pub use self::idle::IdleHandler;
 pub use self::serenity::SerenityHandler;
 pub use self::track_end::TrackEndHandler;
+pub use self::track_error::TrackErrorHandler;
+pub use self::track_start::TrackStartHandler;
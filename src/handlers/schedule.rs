@@ -0,0 +1,224 @@
+use serenity::{client::Context, model::id::GuildId};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    commands::{
+        play::{Mode, get_query_type, normal_query_type_resolver},
+        summon::join_channel,
+    },
+    errors::{ParrotError, verify},
+    guild::{
+        cache::GuildCacheMap,
+        http_client::HttpClientInstance,
+        schedule::{GuildSchedulesMap, Schedule},
+    },
+    messaging::{message::ParrotMessage, messages::SOURCE_INVALID_QUERY},
+};
+
+/// Seconds since the Unix epoch, which is how schedules keep time.
+pub fn get_unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+/// Sets a timer for every schedule the guild has, dropping one-off schedules that were
+/// missed while Parrot was offline and moving weekly ones on to their next run.
+pub async fn arm_schedules(ctx: &Context, guild_id: GuildId) {
+    let now = get_unix_time();
+
+    let schedules = {
+        let mut data = ctx.data.write().await;
+        let Some(guild_schedules) = data
+            .get_mut::<GuildSchedulesMap>()
+            .unwrap()
+            .get_mut(&guild_id)
+        else {
+            return;
+        };
+
+        let count = guild_schedules.schedules.len();
+        guild_schedules
+            .schedules
+            .retain_mut(|schedule| schedule.advance(now));
+
+        if guild_schedules.schedules.len() != count
+            && let Err(err) = guild_schedules.save()
+        {
+            println!(
+                "[ERROR] Failed to save the schedules of guild {} due to {}",
+                guild_id, err
+            );
+        }
+
+        guild_schedules.schedules.clone()
+    };
+
+    for schedule in schedules.iter() {
+        arm_schedule(ctx, guild_id, schedule).await;
+    }
+}
+
+/// Sets a timer for a schedule, replacing the one it had if any.
+pub async fn arm_schedule(ctx: &Context, guild_id: GuildId, schedule: &Schedule) {
+    let delay = Duration::from_secs(schedule.next_run.saturating_sub(get_unix_time()));
+    let (ctx_clone, id) = (ctx.clone(), schedule.id);
+
+    let timer = tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        run_schedule(ctx_clone, guild_id, id).await;
+    });
+
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+
+    if let Some(previous) = cache.schedule_timers.insert(id, timer) {
+        previous.abort();
+    }
+}
+
+/// Calls off a schedule's timer, e.g. because the schedule was removed.
+pub async fn disarm_schedule(ctx: &Context, guild_id: GuildId, id: u32) {
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+
+    if let Some(timer) = cache_map
+        .get_mut(&guild_id)
+        .and_then(|cache| cache.schedule_timers.remove(&id))
+    {
+        timer.abort();
+    }
+}
+
+async fn run_schedule(ctx: Context, guild_id: GuildId, id: u32) {
+    // the timer is done either way, so don't let anyone abort it from here on
+    let (schedule, is_quiz_running) = {
+        let mut data = ctx.data.write().await;
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let cache = cache_map.entry(guild_id).or_default();
+        cache.schedule_timers.remove(&id);
+        let is_quiz_running = cache.quiz.is_some();
+
+        let schedule = data
+            .get::<GuildSchedulesMap>()
+            .unwrap()
+            .get(&guild_id)
+            .and_then(|guild_schedules| guild_schedules.get(id))
+            .cloned();
+
+        (schedule, is_quiz_running)
+    };
+
+    let Some(schedule) = schedule else {
+        return;
+    };
+
+    // the quiz has the queue to itself, so this time round the schedule sits it out
+    if is_quiz_running {
+        println!(
+            "[INFO] Skipped schedule {} in guild {} during a quiz",
+            id, guild_id
+        );
+    } else if let Err(err) = start_scheduled_playback(&ctx, guild_id, &schedule).await {
+        println!(
+            "[ERROR] Failed to start schedule {} in guild {} due to {}",
+            id, guild_id, err
+        );
+    }
+
+    let next = {
+        let mut data = ctx.data.write().await;
+        let Some(guild_schedules) = data
+            .get_mut::<GuildSchedulesMap>()
+            .unwrap()
+            .get_mut(&guild_id)
+        else {
+            return;
+        };
+
+        let now = get_unix_time();
+        let next = match guild_schedules
+            .schedules
+            .iter_mut()
+            .find(|schedule| schedule.id == id)
+        {
+            Some(schedule) if schedule.advance(now) => Some(schedule.clone()),
+            _ => None,
+        };
+
+        if next.is_none() {
+            guild_schedules.remove(id);
+        }
+
+        if let Err(err) = guild_schedules.save() {
+            println!(
+                "[ERROR] Failed to save the schedules of guild {} due to {}",
+                guild_id, err
+            );
+        }
+
+        next
+    };
+
+    if let Some(schedule) = next {
+        arm_schedule(&ctx, guild_id, &schedule).await;
+    }
+}
+
+/// Queues a schedule's playlist or station. Parrot joins the schedule's channel unless it's
+/// already playing somewhere in the guild, in which case the listeners there get it instead.
+async fn start_scheduled_playback(
+    ctx: &Context,
+    guild_id: GuildId,
+    schedule: &Schedule,
+) -> Result<(), ParrotError> {
+    let manager = songbird::get(ctx).await.unwrap();
+
+    let connected_call = match manager.get(guild_id) {
+        Some(call) if call.lock().await.current_channel().is_some() => Some(call),
+        _ => None,
+    };
+
+    let call = match connected_call {
+        Some(call) => call,
+        None => join_channel(ctx, guild_id, schedule.channel_id, schedule.channel_id).await?,
+    };
+
+    let http_client = {
+        let data = ctx.data.read().await;
+        data.get::<HttpClientInstance>().unwrap().clone()
+    };
+
+    let (query_type, _) = get_query_type(&ctx.data, &http_client, &schedule.query).await?;
+    let query_type = verify(query_type, ParrotError::Other(SOURCE_INVALID_QUERY))?;
+
+    normal_query_type_resolver(
+        &call,
+        &ctx.http,
+        &ctx.data,
+        guild_id,
+        &query_type,
+        Mode::End,
+        Some(schedule.created_by),
+    )
+    .await?;
+
+    // voice channels have their own text chat, which is where the listeners are
+    let text_channel = {
+        let data = ctx.data.read().await;
+        data.get::<GuildCacheMap>()
+            .unwrap()
+            .get(&guild_id)
+            .and_then(|cache| cache.text_channel)
+            .unwrap_or(schedule.channel_id)
+    };
+
+    let message = ParrotMessage::ScheduleStarted {
+        query: schedule.query.clone(),
+    };
+    text_channel.say(&ctx.http, message.to_string()).await?;
+
+    Ok(())
+}
@@ -1,25 +1,38 @@
 use crate::{
     commands::{
-        autopause::*, clear::*, leave::*, manage_sources::*, now_playing::*, pause::*, play::*,
-        queue::*, remove::*, repeat::*, repeat_queue::*, resume::*, seek::*, shuffle::*, skip::*,
-        stop::*, summon::*, version::*, voteskip::*,
+        autopause::*, clear::*, favorites::*, leave::*, loop_mode::*, manage_sources::*,
+        now_playing::*, pause::*, play::*, queue::*, quiz::*, remove::*, resume::*, schedule::*,
+        seek::*, settings::*, shuffle::*, skip::*, sleep::*, spotify::*, stats::*, stop::*,
+        summon::*, undo::*, version::*, voteskip::*,
     },
-    connection::{Connection, check_voice_connections},
+    connection::{check_command_connection, check_voice_connections},
     errors::ParrotError,
-    guild::settings::{GuildSettings, GuildSettingsMap},
-    handlers::track_end::update_queue_messages,
-    sources::spotify::{SPOTIFY, Spotify},
-    utils::create_response_text,
+    guild::{
+        cache::GuildCacheMap,
+        play_log::prune_play_log,
+        schedule::{GuildSchedules, GuildSchedulesMap},
+        settings::{GuildSettings, GuildSettingsMap},
+    },
+    handlers::{
+        alone::{cancel_alone_timer, update_alone_timer},
+        schedule::{arm_schedules, get_unix_time},
+        sleep::cancel_sleep_timer,
+        track_end::update_queue_messages,
+    },
+    permissions::{check_bound_channel, check_permissions},
+    sources::spotify::Spotify,
+    utils::{create_ephemeral_response_text, create_response_text},
 };
 use serenity::{
     all::{
-        ActivityData, Command, CommandInteraction, CommandOptionType, CreateCommand,
-        CreateCommandOption, EditMember, Interaction,
+        ActivityData, ChannelType, Command, CommandInteraction, CommandOptionType,
+        ComponentInteraction, CreateCommand, CreateCommandOption, CreateEmbed,
+        CreateInteractionResponse, CreateInteractionResponseMessage, EditMember, Interaction,
+        Permissions,
     },
     async_trait,
     client::{Context, EventHandler},
     model::{gateway::Ready, id::GuildId, prelude::VoiceState},
-    prelude::Mentionable,
 };
 
 pub struct SerenityHandler;
@@ -33,44 +46,109 @@ impl EventHandler for SerenityHandler {
         let activity = ActivityData::listening("/play");
         ctx.set_activity(Some(activity));
 
-        // attempts to authenticate to spotify
-        *SPOTIFY.lock().await = Spotify::auth().await;
+        // keeps the spotify client authenticated in the background
+        Spotify::spawn_manager();
 
         // creates the global application commands
         self.create_commands(&ctx).await;
 
         // loads serialized guild settings
         self.load_guilds_settings(&ctx, &ready).await;
+
+        // loads serialized schedules and sets their timers again
+        self.load_guilds_schedules(&ctx, &ready).await;
+
+        // drops plays that fell out of the play logs' retention
+        self.prune_play_logs(&ready);
+
+        // brings back the guilds in 24/7 mode
+        self.rejoin_home_channels(&ctx, &ready).await;
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        let Interaction::Command(mut command) = interaction else {
-            return;
-        };
-
-        if let Err(err) = self.run_command(&ctx, &mut command).await {
-            self.handle_error(&ctx, &mut command, err).await
+        match interaction {
+            Interaction::Command(mut command) => {
+                if let Err(err) = self.run_command(&ctx, &mut command).await {
+                    self.handle_error(&ctx, &mut command, err).await
+                }
+            }
+            // other buttons are picked up by the collectors of the messages they're on
+            Interaction::Component(component)
+                if component
+                    .data
+                    .custom_id
+                    .starts_with(NOW_PLAYING_BUTTON_PREFIX) =>
+            {
+                if let Err(err) = now_playing_button(&ctx, &component).await {
+                    self.handle_component_error(&ctx, &component, err).await
+                }
+            }
+            Interaction::Component(component)
+                if component.data.custom_id.starts_with(QUEUE_ACTION_PREFIX) =>
+            {
+                if let Err(err) = queue_action(&ctx, &component).await {
+                    self.handle_component_error(&ctx, &component, err).await
+                }
+            }
+            _ => {}
         }
     }
 
     async fn voice_state_update(&self, ctx: Context, _old: Option<VoiceState>, new: VoiceState) {
-        // do nothing if this is a voice update event for a user, not a bot
-        if new.user_id != ctx.cache.current_user().id {
+        let Some(guild_id) = new.guild_id else {
             return;
+        };
+
+        // someone else joined or left, which matters if parrot is left on its own
+        if new.user_id != ctx.cache.current_user().id {
+            return update_alone_timer(&ctx, guild_id).await;
         }
 
-        if new.channel_id.is_some() {
+        if let Some(channel_id) = new.channel_id {
+            // a moderator dragged parrot somewhere else
+            if let Err(err) = follow_channel_move(&ctx, guild_id, channel_id).await {
+                println!(
+                    "[ERROR] Failed to follow the move in guild {} due to {}",
+                    guild_id, err
+                );
+            }
+
+            // the new channel might be just as empty as the old one was full, or vice versa
+            update_alone_timer(&ctx, guild_id).await;
             return self.self_deafen(&ctx, new.guild_id, new).await;
         }
 
         let manager = songbird::get(&ctx).await.unwrap();
-        let guild_id = new.guild_id.unwrap();
+        cancel_alone_timer(&ctx.data, guild_id).await;
+        cancel_sleep_timer(&ctx.data, guild_id).await;
+        cancel_quiz(&ctx.data, guild_id).await;
+
+        let expected_disconnect = {
+            let mut data = ctx.data.write().await;
+            let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+            let cache = cache_map.entry(guild_id).or_default();
+            cache.voice_channel = None;
+            std::mem::take(&mut cache.expected_disconnect)
+        };
 
-        if manager.get(guild_id).is_some() {
-            manager.remove(guild_id).await.ok();
+        // otherwise the connection might only have dropped for a moment, which the driver's
+        // disconnect handler recovers from, or cleans up after if parrot was kicked
+        if expected_disconnect {
+            if manager.get(guild_id).is_some() {
+                manager.remove(guild_id).await.ok();
+            }
+
+            update_queue_messages(&ctx.http, &ctx.data, &[], guild_id).await;
+            return;
         }
 
-        update_queue_messages(&ctx.http, &ctx.data, &[], guild_id).await;
+        // someone kicked parrot out, which 24/7 mode doesn't put up with
+        if let Err(err) = rejoin_home_channel(&ctx, guild_id).await {
+            println!(
+                "[ERROR] Failed to rejoin the home channel of guild {} due to {}",
+                guild_id, err
+            );
+        }
     }
 }
 
@@ -82,8 +160,65 @@ impl SerenityHandler {
                 CreateCommand::new("autopause")
                     .description("Toggles whether to pause after a song ends"),
                 CreateCommand::new("clear").description("Clears the queue"),
+                CreateCommand::new("favorites")
+                    .description("Plays or manages the tracks you've liked")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "play",
+                            "Adds all of your favorites to the queue",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Boolean,
+                                "shuffle",
+                                "Whether to queue them in a random order",
+                            )
+                            .required(false),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "list",
+                            "Lists your favorites",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "page",
+                                "The page to show",
+                            )
+                            .required(false)
+                            .min_int_value(1),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "remove",
+                            "Removes a track from your favorites",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "index",
+                                "The track's position in your favorites",
+                            )
+                            .required(true)
+                            .min_int_value(1),
+                        ])),
+                    ])),
                 CreateCommand::new("leave")
                     .description("Leave the voice channel the bot is connected to"),
+                CreateCommand::new("like").description("Saves the current track to your favorites"),
+                CreateCommand::new("loop")
+                    .description("Changes what gets played again once it's over")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "mode",
+                        "What to loop",
+                    )
+                    .required(true)
+                    .add_string_choice("Off", "off")
+                    .add_string_choice("Current track", "track")
+                    .add_string_choice("Queue", "queue")])),
                 CreateCommand::new("managesources")
                     .description("Manage streaming from different sources"),
                 CreateCommand::new("np")
@@ -166,7 +301,81 @@ impl SerenityHandler {
                             .required(true),
                         ])),
                     ])),
-                CreateCommand::new("queue").description("Shows the queue"),
+                CreateCommand::new("queue")
+                    .description("Shows or searches the queue")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "show",
+                            "Shows the queue",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "find",
+                            "Finds tracks in the queue by title or channel",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "query",
+                                "What to look for",
+                            )
+                            .required(true),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "dedupe",
+                            "Removes tracks that are already queued earlier on",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "removeuser",
+                            "Removes every upcoming track someone queued",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::User,
+                                "user",
+                                "Whose tracks to remove, leave empty for your own",
+                            )
+                            .required(false),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "removeunavailable",
+                            "Removes tracks that failed to load",
+                        ),
+                    ])),
+                CreateCommand::new("quiz")
+                    .description("Plays a game of guessing tracks from a playlist")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "start",
+                            "Starts a quiz",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "playlist",
+                                "The playlist to pick tracks from",
+                            )
+                            .required(true),
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "rounds",
+                                "How many tracks to guess, defaults to 10",
+                            )
+                            .required(false)
+                            .min_int_value(1)
+                            .max_int_value(MAX_QUIZ_ROUNDS as u64),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "stop",
+                            "Stops the quiz that's going on",
+                        ),
+                    ])),
                 CreateCommand::new("remove")
                     .description("Removes a track from the queue")
                     .set_options(Vec::from([
@@ -185,9 +394,76 @@ impl SerenityHandler {
                         .required(true)
                         .min_int_value(1),
                     ])),
-                CreateCommand::new("repeat").description("Toggles looping for the current track"),
-                CreateCommand::new("repeatqueue").description("Toggles looping for the queue"),
                 CreateCommand::new("resume").description("Resumes the current track"),
+                CreateCommand::new("schedule")
+                    .description("Starts playing on its own at a given time")
+                    .default_member_permissions(Permissions::MANAGE_GUILD)
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "add",
+                            "Schedules a playlist or station to start in a voice channel",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Channel,
+                                "channel",
+                                "The voice channel to play in",
+                            )
+                            .required(true)
+                            .channel_types(Vec::from([ChannelType::Voice, ChannelType::Stage])),
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "time",
+                                "When to start, like 21:30, in UTC",
+                            )
+                            .required(true),
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "query",
+                                "The playlist or station to play",
+                            )
+                            .required(true),
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "day",
+                                "The day to start on, defaults to the next time it's that time",
+                            )
+                            .required(false)
+                            .add_int_choice("Monday", 0)
+                            .add_int_choice("Tuesday", 1)
+                            .add_int_choice("Wednesday", 2)
+                            .add_int_choice("Thursday", 3)
+                            .add_int_choice("Friday", 4)
+                            .add_int_choice("Saturday", 5)
+                            .add_int_choice("Sunday", 6),
+                            CreateCommandOption::new(
+                                CommandOptionType::Boolean,
+                                "weekly",
+                                "Whether to start again every week",
+                            )
+                            .required(false),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "list",
+                            "Lists what's scheduled",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "remove",
+                            "Removes a schedule",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "id",
+                                "The schedule's number, as shown in the list",
+                            )
+                            .required(true)
+                            .min_int_value(1),
+                        ])),
+                    ])),
                 CreateCommand::new("seek")
                     .description("Seeks current track to the given position")
                     .set_options(Vec::from([CreateCommandOption::new(
@@ -196,6 +472,211 @@ impl SerenityHandler {
                         "Timestamp in the format HH:MM:SS",
                     )
                     .required(true)])),
+                CreateCommand::new("settings")
+                    .description("Change how Parrot behaves in this server")
+                    .default_member_permissions(Permissions::MANAGE_GUILD)
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "alwayson",
+                            "Keep Parrot in a voice channel around the clock",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Boolean,
+                                "enabled",
+                                "Whether 24/7 mode is on",
+                            )
+                            .required(true),
+                            CreateCommandOption::new(
+                                CommandOptionType::Channel,
+                                "channel",
+                                "The voice channel to stay in, defaults to the one you're in",
+                            )
+                            .required(false)
+                            .channel_types(Vec::from([ChannelType::Voice, ChannelType::Stage])),
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "playlist",
+                                "A playlist or station to play whenever the queue ends",
+                            )
+                            .required(false),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "announce",
+                            "Announce every track as it starts, with buttons to control playback",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Channel,
+                                "channel",
+                                "The text channel to announce in, leave out to stop announcing",
+                            )
+                            .required(false)
+                            .channel_types(Vec::from([ChannelType::Text])),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommandGroup,
+                            "channel",
+                            "Limit music commands to certain text channels",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::SubCommand,
+                                "add",
+                                "Allow music commands in a channel",
+                            )
+                            .set_sub_options(Vec::from([
+                                CreateCommandOption::new(
+                                    CommandOptionType::Channel,
+                                    "channel",
+                                    "The text channel",
+                                )
+                                .required(true)
+                                .channel_types(Vec::from([ChannelType::Text])),
+                            ])),
+                            CreateCommandOption::new(
+                                CommandOptionType::SubCommand,
+                                "remove",
+                                "Stop allowing music commands in a channel",
+                            )
+                            .set_sub_options(Vec::from([
+                                CreateCommandOption::new(
+                                    CommandOptionType::Channel,
+                                    "channel",
+                                    "The text channel",
+                                )
+                                .required(true)
+                                .channel_types(Vec::from([ChannelType::Text])),
+                            ])),
+                            CreateCommandOption::new(
+                                CommandOptionType::SubCommand,
+                                "list",
+                                "Shows where music commands may be used",
+                            ),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "djrole",
+                            "Limit DJ commands to a role, or open them to everyone",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Role,
+                                "role",
+                                "The DJ role, leave empty to remove it",
+                            )
+                            .required(false),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "duplicates",
+                            "What to do when a track is already in the queue",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "policy",
+                                "How to handle duplicates",
+                            )
+                            .required(true)
+                            .add_string_choice("Queue them anyway", "allow")
+                            .add_string_choice("Queue them with a warning", "warn")
+                            .add_string_choice("Refuse them", "refuse"),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "fallback",
+                            "What to try when a track can't be loaded",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "mode",
+                                "The fallback to use",
+                            )
+                            .required(true)
+                            .add_string_choice("Off", "off")
+                            .add_string_choice("Retry with a simpler search", "requery")
+                            .add_string_choice("Search on SoundCloud", "alternate")
+                            .add_string_choice("Both", "both"),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "permission",
+                            "Change who may use a command",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "command",
+                                "The name of the command, e.g. skip",
+                            )
+                            .required(true),
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "level",
+                                "Who may use it",
+                            )
+                            .required(true)
+                            .add_string_choice("Everyone", "everyone")
+                            .add_string_choice("DJs", "dj")
+                            .add_string_choice("Admins", "admin")
+                            .add_string_choice("Default", "default"),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "stage",
+                            "Change how Parrot behaves in stage channels",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Boolean,
+                                "topic",
+                                "Whether to set the stage topic to the current track",
+                            )
+                            .required(true),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "timeouts",
+                            "Change when Parrot leaves the voice channel on its own",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "idle",
+                                "Minutes without playing anything before leaving",
+                            )
+                            .required(false)
+                            .min_int_value(1)
+                            .max_int_value(180),
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "alone",
+                                "Seconds alone in the voice channel before leaving",
+                            )
+                            .required(false)
+                            .min_int_value(0)
+                            .max_int_value(3600),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "voteskip",
+                            "Change how many listeners must vote to skip a track",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::Integer,
+                                "percent",
+                                "Percentage of listeners, excluding deafened ones",
+                            )
+                            .required(true)
+                            .min_int_value(1)
+                            .max_int_value(100),
+                        ])),
+                    ])),
                 CreateCommand::new("shuffle").description("Shuffles the queue"),
                 CreateCommand::new("skip")
                     .description("Skips the current track")
@@ -206,8 +687,66 @@ impl SerenityHandler {
                     )
                     .required(false)
                     .min_int_value(1)])),
+                CreateCommand::new("sleep")
+                    .description("Stops and leaves after a while")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "in",
+                            "Stops and leaves once the time is up",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "duration",
+                                "How long until then, like 45m or 1h30m",
+                            )
+                            .required(true),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "aftertrack",
+                            "Stops and leaves once the current track is over",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "cancel",
+                            "Cancels the sleep timer",
+                        ),
+                    ])),
+                CreateCommand::new("spotify")
+                    .description("Manage the connection to Spotify")
+                    .default_member_permissions(Permissions::ADMINISTRATOR)
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "status",
+                            "Shows whether Parrot is authenticated with Spotify",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "reconnect",
+                            "Authenticates with Spotify again right away",
+                        ),
+                    ])),
+                CreateCommand::new("stats")
+                    .description("Shows what's been played the most lately")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "server",
+                            "Shows the server's stats",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "me",
+                            "Shows the stats of the tracks you requested",
+                        ),
+                    ])),
                 CreateCommand::new("stop").description("Stops the bot and clears the queue"),
                 CreateCommand::new("summon").description("Summons the bot in your voice channel"),
+                CreateCommand::new("undo")
+                    .description("Undoes the last clear, shuffle or removal from the queue"),
                 CreateCommand::new("version").description("Displays the current version"),
                 CreateCommand::new("voteskip")
                     .description("Starts a vote to skip the current track"),
@@ -237,6 +776,53 @@ impl SerenityHandler {
         }
     }
 
+    async fn load_guilds_schedules(&self, ctx: &Context, ready: &Ready) {
+        println!("[INFO] Loading guilds' schedules");
+        let mut data = ctx.data.write().await;
+        for guild in &ready.guilds {
+            let schedules = data.get_mut::<GuildSchedulesMap>().unwrap();
+
+            let guild_schedules = schedules
+                .entry(guild.id)
+                .or_insert_with(|| GuildSchedules::new(guild.id));
+
+            if let Err(err) = guild_schedules.load_if_exists() {
+                println!(
+                    "[ERROR] Failed to load guild {} schedules due to {}",
+                    guild.id, err
+                );
+            }
+        }
+        drop(data);
+
+        for guild in &ready.guilds {
+            arm_schedules(ctx, guild.id).await;
+        }
+    }
+
+    fn prune_play_logs(&self, ready: &Ready) {
+        let now = get_unix_time();
+        for guild in &ready.guilds {
+            if let Err(err) = prune_play_log(guild.id, now) {
+                println!(
+                    "[ERROR] Failed to prune guild {} play log due to {}",
+                    guild.id, err
+                );
+            }
+        }
+    }
+
+    async fn rejoin_home_channels(&self, ctx: &Context, ready: &Ready) {
+        for guild in &ready.guilds {
+            if let Err(err) = rejoin_home_channel(ctx, guild.id).await {
+                println!(
+                    "[ERROR] Failed to rejoin the home channel of guild {} due to {}",
+                    guild.id, err
+                );
+            }
+        }
+    }
+
     async fn run_command(
         &self,
         ctx: &Context,
@@ -258,63 +844,50 @@ impl SerenityHandler {
             }
         }
 
+        // configuration commands stay usable everywhere so a binding can always be undone
+        if !matches!(
+            command_name,
+            "managesources" | "settings" | "spotify" | "version"
+        ) {
+            check_bound_channel(ctx, guild_id, command.channel_id).await?;
+        }
+
         // fetch the user and the bot's user IDs
         let user_id = command.user.id;
         let bot_id = ctx.cache.current_user().id;
 
-        match command_name {
-            "autopause" | "clear" | "leave" | "pause" | "remove" | "repeat" | "repeatqueue"
-            | "resume" | "seek" | "shuffle" | "skip" | "stop" | "voteskip" => {
-                match check_voice_connections(&guild, &user_id, &bot_id) {
-                    Connection::User(_) | Connection::Neither => Err(ParrotError::NotConnected),
-                    Connection::Bot(bot_channel_id) => {
-                        Err(ParrotError::AuthorDisconnected(bot_channel_id.mention()))
-                    }
-                    Connection::Separate(_, _) => Err(ParrotError::WrongVoiceChannel),
-                    _ => Ok(()),
-                }
-            }
-            "play" | "superplay" | "summon" => {
-                match check_voice_connections(&guild, &user_id, &bot_id) {
-                    Connection::User(_) => Ok(()),
-                    Connection::Bot(_) if command_name == "summon" => {
-                        Err(ParrotError::AuthorNotFound)
-                    }
-                    Connection::Bot(_) if command_name != "summon" => {
-                        Err(ParrotError::WrongVoiceChannel)
-                    }
-                    Connection::Separate(bot_channel_id, _) => {
-                        Err(ParrotError::AlreadyConnected(bot_channel_id.mention()))
-                    }
-                    Connection::Neither => Err(ParrotError::AuthorNotFound),
-                    _ => Ok(()),
-                }
-            }
-            "np" | "queue" => match check_voice_connections(&guild, &user_id, &bot_id) {
-                Connection::User(_) | Connection::Neither => Err(ParrotError::NotConnected),
-                _ => Ok(()),
-            },
-            _ => Ok(()),
-        }?;
+        let connection = check_voice_connections(&guild, &user_id, &bot_id);
+        check_command_connection(command_name, connection)?;
+
+        check_permissions(ctx, command).await?;
+        check_quiz_command(&ctx.data, guild_id, command_name).await?;
 
         match command_name {
             "autopause" => autopause(ctx, command).await,
             "clear" => clear(ctx, command).await,
+            "favorites" => favorites(ctx, command).await,
             "leave" => leave(ctx, command).await,
+            "like" => like(ctx, command).await,
+            "loop" => loop_mode(ctx, command).await,
             "managesources" => allow(ctx, command).await,
             "np" => now_playing(ctx, command).await,
             "pause" => pause(ctx, command).await,
             "play" | "superplay" => play(ctx, command).await,
             "queue" => queue(ctx, command).await,
+            "quiz" => quiz(ctx, command).await,
             "remove" => remove(ctx, command).await,
-            "repeat" => repeat(ctx, command).await,
-            "repeatqueue" => repeat_queue(ctx, command).await,
             "resume" => resume(ctx, command).await,
+            "schedule" => schedule(ctx, command).await,
             "seek" => seek(ctx, command).await,
+            "settings" => settings(ctx, command).await,
             "shuffle" => shuffle(ctx, command).await,
             "skip" => skip(ctx, command).await,
+            "sleep" => sleep(ctx, command).await,
+            "spotify" => spotify(ctx, command).await,
+            "stats" => stats(ctx, command).await,
             "stop" => stop(ctx, command).await,
             "summon" => summon(ctx, command, true).await,
+            "undo" => undo(ctx, command).await,
             "version" => version(ctx, command).await,
             "voteskip" => voteskip(ctx, command).await,
             _ => unreachable!(),
@@ -342,7 +915,36 @@ impl SerenityHandler {
         err: ParrotError,
     ) {
         println!("{}", &err);
-        create_response_text(&ctx.http, interaction, &format!("{err}"))
+
+        // only the author needs to know they're in the wrong channel
+        let response = match err {
+            ParrotError::UnboundChannel(_) => {
+                create_ephemeral_response_text(&ctx.http, interaction, &format!("{err}")).await
+            }
+            _ => create_response_text(&ctx.http, interaction, &format!("{err}")).await,
+        };
+
+        response.expect("failed to create response");
+    }
+
+    async fn handle_component_error(
+        &self,
+        ctx: &Context,
+        interaction: &ComponentInteraction,
+        err: ParrotError,
+    ) {
+        println!("{}", &err);
+
+        // the message stays as it was, only the one pressing needs to know why
+        let embed = CreateEmbed::default().description(format!("{err}"));
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .add_embed(embed)
+                .ephemeral(true),
+        );
+
+        interaction
+            .create_response(&ctx.http, response)
             .await
             .expect("failed to create response");
     }
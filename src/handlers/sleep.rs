@@ -0,0 +1,139 @@
+use serenity::{
+    async_trait,
+    client::Context,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use songbird::{Event, EventContext, EventHandler, TrackEvent, tracks::TrackHandle};
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    connection::disconnect,
+    guild::{
+        cache::{GuildCacheMap, SleepTimer},
+        stored_queue::GuildStoredQueueMap,
+    },
+    messaging::messages::SLEEP_ALERT,
+};
+
+/// Puts Parrot to sleep once the track it was added to is over, unless the sleep timer
+/// was cancelled or replaced in the meantime.
+pub struct SleepHandler {
+    pub ctx: Context,
+    pub guild_id: GuildId,
+    pub uuid: String,
+}
+
+#[async_trait]
+impl EventHandler for SleepHandler {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        let is_pending = {
+            let data = self.ctx.data.read().await;
+            let timer = data
+                .get::<GuildCacheMap>()
+                .unwrap()
+                .get(&self.guild_id)
+                .and_then(|cache| cache.sleep_timer.as_ref());
+
+            match timer {
+                Some(SleepTimer::AfterTrack(uuid)) => *uuid == self.uuid,
+                _ => false,
+            }
+        };
+
+        // leaving tears down the call this event is running on, so do it elsewhere
+        if is_pending {
+            tokio::spawn(fall_asleep(self.ctx.clone(), self.guild_id));
+        }
+
+        Some(Event::Cancel)
+    }
+}
+
+/// Stops and leaves once the given time is up, replacing any sleep timer already set.
+pub async fn start_sleep_countdown(ctx: &Context, guild_id: GuildId, duration: Duration) {
+    let ctx_clone = ctx.clone();
+    let countdown = tokio::spawn(async move {
+        tokio::time::sleep(duration).await;
+        fall_asleep(ctx_clone, guild_id).await;
+    });
+
+    set_sleep_timer(&ctx.data, guild_id, SleepTimer::Countdown(countdown)).await;
+}
+
+/// Stops and leaves once the given track is over, replacing any sleep timer already set.
+/// A looping track would never end, so it's made to play just this once more.
+pub async fn sleep_after_track(ctx: &Context, guild_id: GuildId, track: &TrackHandle) {
+    let uuid = track.uuid().to_string();
+    track.disable_loop().ok();
+
+    let handler = SleepHandler {
+        ctx: ctx.clone(),
+        guild_id,
+        uuid: uuid.clone(),
+    };
+    track.add_event(Event::Track(TrackEvent::End), handler).ok();
+
+    set_sleep_timer(&ctx.data, guild_id, SleepTimer::AfterTrack(uuid)).await;
+}
+
+/// Calls off the guild's sleep timer. Returns whether there was one.
+pub async fn cancel_sleep_timer(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> bool {
+    let mut data = data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+
+    let timer = cache_map
+        .get_mut(&guild_id)
+        .and_then(|cache| cache.sleep_timer.take());
+
+    if let Some(SleepTimer::Countdown(countdown)) = &timer {
+        countdown.abort();
+    }
+
+    timer.is_some()
+}
+
+async fn set_sleep_timer(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId, timer: SleepTimer) {
+    let mut data = data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+
+    if let Some(SleepTimer::Countdown(countdown)) = cache.sleep_timer.replace(timer) {
+        countdown.abort();
+    }
+}
+
+async fn fall_asleep(ctx: Context, guild_id: GuildId) {
+    // the timer is done either way, so don't let anyone abort it from here on
+    let text_channel = {
+        let mut data = ctx.data.write().await;
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let cache = cache_map.entry(guild_id).or_default();
+        cache.sleep_timer = None;
+        let text_channel = cache.text_channel;
+
+        if let Some(guild_stored_queue) = data
+            .get_mut::<GuildStoredQueueMap>()
+            .unwrap()
+            .get_mut(&guild_id)
+        {
+            guild_stored_queue.continue_play = false;
+            guild_stored_queue.queue.clear();
+        }
+
+        text_channel
+    };
+
+    let manager = songbird::get(&ctx).await.unwrap();
+    if let Some(call) = manager.get(guild_id) {
+        call.lock().await.queue().stop();
+    }
+
+    if disconnect(&manager, &ctx.data, guild_id).await.is_err() {
+        return;
+    }
+
+    if let Some(channel_id) = text_channel {
+        channel_id.say(&ctx.http, SLEEP_ALERT).await.ok();
+    }
+}
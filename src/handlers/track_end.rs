@@ -5,21 +5,30 @@ use serenity::{
     model::id::GuildId,
     prelude::{Mutex, RwLock, TypeMap},
 };
-use songbird::{Call, Event, EventContext, EventHandler, tracks::TrackHandle};
+use songbird::{
+    Call, Event, EventContext, EventHandler,
+    tracks::{PlayMode, TrackHandle},
+};
 use std::sync::Arc;
 
 use crate::{
     commands::{
-        play::{Mode, normal_query_type_resolver},
+        play::{get_replay_source, play_fallback_playlist},
         queue::{
-            build_single_nav_btn, calculate_num_pages, create_queue_embed, forget_queue_message,
+            build_single_nav_btn, build_track_select, calculate_num_pages, create_queue_embed,
+            forget_queue_message,
         },
         voteskip::forget_skip_votes,
     },
     guild::{
-        cache::GuildCacheMap, metadata_store::MetadataStore, settings::GuildSettingsMap,
+        cache::GuildCacheMap,
+        http_client::HttpClientInstance,
+        metadata_store::MetadataStore,
+        play_log::{MIN_LOGGED_PLAY_TIME, PlayLogEntry, log_play},
+        settings::{GuildSettingsMap, LoopMode},
         stored_queue::GuildStoredQueueMap,
     },
+    handlers::{schedule::get_unix_time, track_error::forget_restarted_tracks},
 };
 
 pub struct TrackEndHandler {
@@ -38,22 +47,41 @@ pub struct ModifyQueueHandler {
 
 #[async_trait]
 impl EventHandler for TrackEndHandler {
-    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
-        let (autopause, queue_loop, guild_stored_queue) = {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        // before anything else gets the chance to forget about the tracks that ended
+        self.log_ended_tracks(ctx).await;
+
+        if let EventContext::Track(track_list) = ctx {
+            let ended: Vec<TrackHandle> = track_list
+                .iter()
+                .filter(|(state, _)| matches!(state.playing, PlayMode::End | PlayMode::Stop))
+                .map(|(_, track)| (*track).clone())
+                .collect();
+            forget_restarted_tracks(&self.ctx_data, self.guild_id, &ended).await;
+        }
+
+        let (autopause, loop_mode, always_on, continue_play) = {
             let data_rlock = self.ctx_data.read().await;
             let guild_setting = data_rlock
                 .get::<GuildSettingsMap>()?
                 .get(&self.guild_id)
                 .unwrap();
-            let guild_stored_queue = data_rlock
+            // tracks from the 24/7 fallback playlist never go through the stored queue
+            let continue_play = data_rlock
                 .get::<GuildStoredQueueMap>()?
-                .get(&self.guild_id)?
-                .clone();
+                .get(&self.guild_id)
+                .is_some_and(|guild_stored_queue| guild_stored_queue.continue_play);
+            // a quiz empties the queue between rounds, which isn't the 24/7 playlist's cue
+            let is_quiz_running = data_rlock
+                .get::<GuildCacheMap>()?
+                .get(&self.guild_id)
+                .is_some_and(|cache| cache.quiz.is_some());
 
             (
                 guild_setting.autopause,
-                guild_setting.queue_loop,
-                guild_stored_queue,
+                guild_setting.loop_mode,
+                guild_setting.always_on && !is_quiz_running,
+                continue_play,
             )
         };
 
@@ -63,28 +91,8 @@ impl EventHandler for TrackEndHandler {
             local_queue.pause().ok();
         }
 
-        if queue_loop && guild_stored_queue.continue_play {
-            let is_queue_empty = {
-                let handler = self.call.lock().await;
-                handler.queue().is_empty()
-            };
-
-            if is_queue_empty {
-                for item in guild_stored_queue.queue {
-                    if let Err(err) = normal_query_type_resolver(
-                        &self.call,
-                        &self.http,
-                        &self.ctx_data,
-                        self.guild_id,
-                        &item,
-                        Mode::End,
-                    )
-                    .await
-                    {
-                        println!("{}", err);
-                    }
-                }
-            }
+        if loop_mode == LoopMode::Queue && continue_play {
+            self.requeue_ended_tracks(ctx).await;
         } else {
             let current_track = {
                 let handler = self.call.lock().await;
@@ -100,10 +108,134 @@ impl EventHandler for TrackEndHandler {
 
         forget_skip_votes(&self.ctx_data, self.guild_id).await.ok();
 
+        if always_on && self.is_queue_finished(ctx).await {
+            let result =
+                play_fallback_playlist(&self.call, &self.http, &self.ctx_data, self.guild_id).await;
+
+            if let Err(err) = result {
+                println!(
+                    "[ERROR] Failed to play the fallback playlist due to {}",
+                    err
+                );
+            }
+        }
+
         None
     }
 }
 
+impl TrackEndHandler {
+    /// Writes the tracks that ended to the guild's play log, leaving out those that
+    /// were barely listened to.
+    async fn log_ended_tracks(&self, ctx: &EventContext<'_>) {
+        let EventContext::Track(track_list) = ctx else {
+            return;
+        };
+
+        let ended_at = get_unix_time();
+        let entries: Vec<PlayLogEntry> = {
+            let data = self.ctx_data.read().await;
+            let metadata_store = data.get::<MetadataStore>().unwrap();
+
+            track_list
+                .iter()
+                .filter(|(state, _)| matches!(state.playing, PlayMode::End | PlayMode::Stop))
+                .filter(|(state, _)| state.play_time >= MIN_LOGGED_PLAY_TIME)
+                .filter_map(|(state, track)| {
+                    let uuid = track.uuid().to_string();
+                    let metadata = metadata_store.retrieve_metadata(&uuid)?;
+
+                    Some(PlayLogEntry {
+                        title: metadata.title.clone()?,
+                        source_url: metadata.source_url.clone(),
+                        requester: metadata_store.retrieve_requester(&uuid),
+                        ended_at,
+                        play_time: state.play_time.as_secs(),
+                    })
+                })
+                .collect()
+        };
+
+        for entry in entries {
+            if let Err(err) = log_play(self.guild_id, &entry) {
+                println!(
+                    "[ERROR] Failed to log a play in guild {} due to {}",
+                    self.guild_id, err
+                );
+            }
+        }
+    }
+
+    /// Puts tracks that played through or got skipped back at the end of the queue,
+    /// carrying their metadata and requester over so nothing has to be looked up again.
+    async fn requeue_ended_tracks(&self, ctx: &EventContext<'_>) {
+        let EventContext::Track(track_list) = ctx else {
+            return;
+        };
+
+        let http_client = {
+            let data = self.ctx_data.read().await;
+            data.get::<HttpClientInstance>().unwrap().clone()
+        };
+
+        for (state, track) in track_list.iter() {
+            // errored tracks would only fail again
+            if !matches!(state.playing, PlayMode::End | PlayMode::Stop) {
+                continue;
+            }
+
+            let uuid = track.uuid().to_string();
+            let (metadata, requester) = {
+                let data = self.ctx_data.read().await;
+                let metadata_store = data.get::<MetadataStore>().unwrap();
+                (
+                    metadata_store.retrieve_metadata(&uuid).cloned(),
+                    metadata_store.retrieve_requester(&uuid),
+                )
+            };
+
+            let Some(metadata) = metadata else {
+                continue;
+            };
+            let Some(source_url) = metadata.source_url.clone() else {
+                continue;
+            };
+
+            let input = get_replay_source(http_client.clone(), source_url);
+            let track_handle = self.call.lock().await.enqueue_input(input).await;
+            let new_uuid = track_handle.uuid().to_string();
+
+            let mut data = self.ctx_data.write().await;
+            let metadata_store = data.get_mut::<MetadataStore>().unwrap();
+            metadata_store.remove_metadata(&uuid);
+            metadata_store.insert_metadata(new_uuid.clone(), metadata);
+
+            if let Some(requester) = requester {
+                metadata_store.insert_requester(new_uuid, requester);
+            }
+        }
+    }
+
+    /// Whether nothing but the tracks that just ended is left, since the queue
+    /// might not have gotten around to removing them yet.
+    async fn is_queue_finished(&self, ctx: &EventContext<'_>) -> bool {
+        let ended = match ctx {
+            EventContext::Track(track_list) => track_list
+                .iter()
+                .map(|(_, track)| track.uuid())
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+
+        let handler = self.call.lock().await;
+        handler
+            .queue()
+            .current_queue()
+            .iter()
+            .all(|track| ended.contains(&track.uuid()))
+    }
+}
+
 #[async_trait]
 impl EventHandler for ModifyQueueHandler {
     async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
@@ -125,9 +257,10 @@ pub async fn update_queue_messages(
     let data = ctx_data.read().await;
     let cache_map = data.get::<GuildCacheMap>().unwrap();
 
+    // the queue holds the answers while a quiz is going on, so it stays out of sight
     let mut messages = match cache_map.get(&guild_id) {
-        Some(cache) => cache.queue_messages.clone(),
-        None => return,
+        Some(cache) if cache.quiz.is_none() => cache.queue_messages.clone(),
+        _ => return,
     };
     drop(data);
 
@@ -137,13 +270,19 @@ pub async fn update_queue_messages(
         let mut page = page_lock.write().await;
         *page = usize::min(*page, num_pages - 1);
 
-        let embed = create_queue_embed(tracks, *page, ctx_data).await;
+        let embed = create_queue_embed(tracks, *page, guild_id, ctx_data).await;
+        let track_select = build_track_select(tracks, *page, ctx_data).await;
 
         if let Ok(embed) = embed {
             let edit_message = message
                 .edit(
                     &http,
-                    build_nav_btns(EditMessage::new().add_embed(embed), *page, num_pages),
+                    build_nav_btns(
+                        EditMessage::new().add_embed(embed),
+                        *page,
+                        num_pages,
+                        track_select,
+                    ),
                 )
                 .await;
 
@@ -154,15 +293,21 @@ pub async fn update_queue_messages(
     }
 }
 
-pub fn build_nav_btns(message: EditMessage, page: usize, num_pages: usize) -> EditMessage {
+pub fn build_nav_btns(
+    message: EditMessage,
+    page: usize,
+    num_pages: usize,
+    track_select: Option<CreateActionRow>,
+) -> EditMessage {
     let (cant_left, cant_right) = (page < 1, page >= num_pages - 1);
 
-    let components = vec![CreateActionRow::Buttons(vec![
+    let mut components = vec![CreateActionRow::Buttons(vec![
         build_single_nav_btn("<<", cant_left),
         build_single_nav_btn("<", cant_left),
         build_single_nav_btn(">", cant_right),
         build_single_nav_btn(">>", cant_right),
     ])];
+    components.extend(track_select);
 
     message.components(components)
 }
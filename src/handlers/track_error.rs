@@ -0,0 +1,206 @@
+use serenity::{
+    all::{ChannelId, CreateEmbed, CreateMessage},
+    async_trait,
+    http::Http,
+    model::id::GuildId,
+    prelude::{Mutex, RwLock, TypeMap},
+};
+use songbird::{
+    Call, Event, EventContext, EventHandler,
+    input::AuxMetadata,
+    tracks::{PlayMode, TrackHandle},
+};
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    commands::play::{QueryType, get_track_source},
+    errors::{ParrotError, verify},
+    guild::{cache::GuildCacheMap, http_client::HttpClientInstance, metadata_store::MetadataStore},
+    messaging::messages::{TRACK_ERROR_SKIPPED, TRACK_ERROR_UNKNOWN},
+};
+
+pub struct TrackErrorHandler {
+    pub http: Arc<Http>,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub call: Arc<Mutex<Call>>,
+    pub ctx_data: Arc<RwLock<TypeMap>>,
+}
+
+#[async_trait]
+impl EventHandler for TrackErrorHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+
+        for (state, track) in track_list.iter() {
+            let PlayMode::Errored(play_error) = &state.playing else {
+                continue;
+            };
+
+            println!(
+                "[ERROR] track {} stopped playing: {:?}",
+                track.uuid(),
+                play_error
+            );
+
+            let metadata = {
+                let data = self.ctx_data.read().await;
+                data.get::<MetadataStore>()?
+                    .retrieve_metadata(&track.uuid().to_string())
+                    .cloned()
+            };
+
+            // most mid-song failures are expired stream links, which a fresh input fixes
+            match self.restart_track(track, state.position, &metadata).await {
+                Ok(_) => continue,
+                Err(err) => println!("[INFO] not restarting {}: {}", track.uuid(), err),
+            }
+
+            self.skip_track(track).await;
+
+            let title = metadata
+                .and_then(|metadata| metadata.title)
+                .unwrap_or_else(|| TRACK_ERROR_UNKNOWN.to_string());
+
+            let embed = CreateEmbed::default().description(format!(
+                "{} **{}**\n{}",
+                TRACK_ERROR_SKIPPED,
+                title,
+                ParrotError::TrackFail(play_error.clone())
+            ));
+
+            self.channel_id
+                .send_message(&self.http, CreateMessage::new().embed(embed))
+                .await
+                .ok();
+        }
+
+        None
+    }
+}
+
+impl TrackErrorHandler {
+    /// Swaps the errored track for a freshly loaded copy that picks up where it left off.
+    /// Each track only gets one restart, a stream that dies twice is unlikely to recover.
+    async fn restart_track(
+        &self,
+        track: &TrackHandle,
+        position: Duration,
+        metadata: &Option<AuxMetadata>,
+    ) -> Result<(), ParrotError> {
+        let uuid = track.uuid().to_string();
+
+        let (already_restarted, http_client) = {
+            let mut data = self.ctx_data.write().await;
+            let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+            let cache = cache_map.entry(self.guild_id).or_default();
+            let already_restarted = cache.restarted_tracks.remove(&uuid);
+
+            (
+                already_restarted,
+                data.get::<HttpClientInstance>().unwrap().clone(),
+            )
+        };
+
+        verify(
+            !already_restarted,
+            ParrotError::Other("track was already restarted"),
+        )?;
+
+        let metadata = verify(
+            metadata.clone(),
+            ParrotError::Other("track has no metadata"),
+        )?;
+        let source_url = verify(
+            metadata.source_url.clone(),
+            ParrotError::Other("track has no source url"),
+        )?;
+
+        let query_type = QueryType::from_source_url(source_url);
+        let (input, _) = get_track_source(http_client, query_type).await?;
+
+        let mut handler = self.call.lock().await;
+        let restarted = handler.enqueue_input(input).await;
+
+        handler.queue().modify_queue(|queue| {
+            let Some(restarted) = queue.pop_back() else {
+                return;
+            };
+
+            // the queue may have already moved on to the next track, hold it back for now
+            queue.retain(|queued| queued.uuid() != track.uuid());
+            if let Some(next) = queue.front() {
+                next.pause().ok();
+            }
+
+            queue.push_front(restarted);
+        });
+        drop(handler);
+
+        restarted.play().ok();
+        let _ = restarted.seek(position);
+
+        let restarted_uuid = restarted.uuid().to_string();
+        let mut data = self.ctx_data.write().await;
+
+        let metadata_store = data.get_mut::<MetadataStore>().unwrap();
+        if let Some(requester) = metadata_store.retrieve_requester(&uuid) {
+            metadata_store.insert_requester(restarted_uuid.clone(), requester);
+        }
+        metadata_store.remove_metadata(&uuid);
+        metadata_store.insert_metadata(restarted_uuid.clone(), metadata);
+
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        let cache = cache_map.entry(self.guild_id).or_default();
+        cache.restarted_tracks.insert(restarted_uuid);
+
+        println!("[INFO] restarted {} at {:?}", uuid, position);
+        Ok(())
+    }
+
+    async fn skip_track(&self, track: &TrackHandle) {
+        let handler = self.call.lock().await;
+
+        // songbird's own queue handler might have beaten us to it, so only
+        // move on if the errored track is still the one up front
+        handler.queue().modify_queue(|queue| {
+            if queue
+                .front()
+                .is_some_and(|front| front.uuid() == track.uuid())
+            {
+                queue.pop_front();
+
+                if let Some(next) = queue.front() {
+                    next.play().ok();
+                }
+            }
+        });
+        drop(handler);
+
+        let mut data = self.ctx_data.write().await;
+        let metadata_store = data.get_mut::<MetadataStore>().unwrap();
+        metadata_store.remove_metadata(&track.uuid().to_string());
+    }
+}
+
+/// Forgets that tracks were restarted once they've left the queue, whichever way they went.
+pub async fn forget_restarted_tracks(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    tracks: &[TrackHandle],
+) {
+    let mut data = data.write().await;
+
+    let Some(cache) = data
+        .get_mut::<GuildCacheMap>()
+        .and_then(|cache_map| cache_map.get_mut(&guild_id))
+    else {
+        return;
+    };
+
+    for track in tracks {
+        cache.restarted_tracks.remove(&track.uuid().to_string());
+    }
+}
@@ -0,0 +1,209 @@
+use serenity::{
+    all::{ChannelId, CreateMessage, EditMessage, GetMessages, Message},
+    async_trait,
+    client::Context,
+    model::id::GuildId,
+};
+use songbird::{
+    Event, EventContext, EventHandler,
+    tracks::{LoopState, TrackHandle},
+};
+
+use crate::{
+    commands::{now_playing::build_now_playing_btns, quiz::is_quiz_running},
+    connection::{is_stage_channel, update_stage_topic},
+    errors::ParrotError,
+    guild::{
+        cache::{GuildCacheMap, SleepTimer},
+        metadata_store::MetadataStore,
+        settings::{GuildSettingsMap, LoopMode},
+    },
+    handlers::live_message::track_live_message,
+    utils::create_now_playing_embed,
+};
+
+/// Announces tracks as they start and keeps the stage topic in line with what's playing,
+/// for guilds that asked for either. Tracks also pick up the guild's track loop here.
+pub struct TrackStartHandler {
+    pub ctx: Context,
+    pub guild_id: GuildId,
+}
+
+#[async_trait]
+impl EventHandler for TrackStartHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(track_list) = ctx else {
+            return None;
+        };
+
+        let (state, track) = track_list.first()?;
+
+        // quiz tracks are there to be guessed, and only play a clip at a time anyway
+        if is_quiz_running(&self.ctx.data, self.guild_id).await {
+            return None;
+        }
+
+        let (loop_mode, sleeps_after) = {
+            let data = self.ctx.data.read().await;
+            let loop_mode = data
+                .get::<GuildSettingsMap>()
+                .unwrap()
+                .get(&self.guild_id)
+                .map(|guild_settings| guild_settings.loop_mode)
+                .unwrap_or_default();
+
+            // resuming plays the track again too, which mustn't undo /sleep aftertrack
+            let uuid = track.uuid().to_string();
+            let sleeps_after = data
+                .get::<GuildCacheMap>()
+                .unwrap()
+                .get(&self.guild_id)
+                .and_then(|cache| cache.sleep_timer.as_ref())
+                .is_some_and(
+                    |timer| matches!(timer, SleepTimer::AfterTrack(after) if *after == uuid),
+                );
+
+            (loop_mode, sleeps_after)
+        };
+
+        if loop_mode == LoopMode::Track && state.loops != LoopState::Infinite && !sleeps_after {
+            track.enable_loop().ok();
+        }
+
+        if let Err(err) = self.announce(loop_mode, track).await {
+            println!(
+                "[ERROR] Failed to announce the track in guild {} due to {}",
+                self.guild_id, err
+            );
+        }
+
+        self.update_stage(track).await;
+        None
+    }
+}
+
+impl TrackStartHandler {
+    /// Posts the now playing embed to the announcement channel, editing the previous
+    /// announcement instead if nothing has been said since.
+    async fn announce(&self, loop_mode: LoopMode, track: &TrackHandle) -> Result<(), ParrotError> {
+        let announce_channel = {
+            let data = self.ctx.data.read().await;
+            data.get::<GuildSettingsMap>()
+                .unwrap()
+                .get(&self.guild_id)
+                .and_then(|guild_settings| guild_settings.announce_channel)
+        };
+
+        let Some(channel_id) = announce_channel else {
+            return Ok(());
+        };
+
+        let embed = create_now_playing_embed(track, &self.ctx.data).await?;
+        let buttons = build_now_playing_btns(false, loop_mode);
+
+        let previous = {
+            let mut data = self.ctx.data.write().await;
+            let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+            cache_map
+                .entry(self.guild_id)
+                .or_default()
+                .now_playing_message
+                .take()
+        };
+
+        let message = match previous {
+            Some(mut message) if self.is_latest_message(channel_id, &message).await => {
+                message
+                    .edit(
+                        &self.ctx.http,
+                        EditMessage::new().embed(embed).components(buttons),
+                    )
+                    .await?;
+                message
+            }
+            previous => {
+                // an announcement buried under the conversation is no use to anyone
+                if let Some(message) = previous {
+                    message.delete(&self.ctx.http).await.ok();
+                }
+
+                channel_id
+                    .send_message(
+                        &self.ctx.http,
+                        CreateMessage::new().embed(embed).components(buttons),
+                    )
+                    .await?
+            }
+        };
+
+        let mut data = self.ctx.data.write().await;
+        let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+        cache_map
+            .entry(self.guild_id)
+            .or_default()
+            .now_playing_message = Some(message.clone());
+        drop(data);
+
+        track_live_message(&self.ctx, self.guild_id, message).await;
+        Ok(())
+    }
+
+    async fn is_latest_message(&self, channel_id: ChannelId, message: &Message) -> bool {
+        if message.channel_id != channel_id {
+            return false;
+        }
+
+        channel_id
+            .messages(&self.ctx.http, GetMessages::new().limit(1))
+            .await
+            .is_ok_and(|messages| messages.first().is_some_and(|last| last.id == message.id))
+    }
+
+    async fn update_stage(&self, track: &TrackHandle) {
+        let title = {
+            let data = self.ctx.data.read().await;
+            let stage_topic = data
+                .get::<GuildSettingsMap>()
+                .unwrap()
+                .get(&self.guild_id)
+                .is_some_and(|guild_settings| guild_settings.stage_topic);
+
+            if !stage_topic {
+                return;
+            }
+
+            let title = data
+                .get::<MetadataStore>()
+                .unwrap()
+                .retrieve_metadata(&track.uuid().to_string())
+                .and_then(|metadata| metadata.title.clone());
+
+            match title {
+                Some(title) => title,
+                None => return,
+            }
+        };
+
+        // parrot might have been moved since it joined, so ask where it is now
+        let manager = songbird::get(&self.ctx).await.unwrap();
+        let Some(call) = manager.get(self.guild_id) else {
+            return;
+        };
+
+        let Some(channel_id) = call.lock().await.current_channel() else {
+            return;
+        };
+
+        let channel_id: ChannelId = channel_id.0.into();
+        if !is_stage_channel(&self.ctx, self.guild_id, channel_id) {
+            return;
+        }
+
+        if let Err(err) = update_stage_topic(&self.ctx, channel_id, &title).await {
+            println!(
+                "[ERROR] Failed to update the stage topic in guild {} due to {}",
+                self.guild_id, err
+            );
+        }
+    }
+}
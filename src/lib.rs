@@ -5,6 +5,7 @@ pub mod errors;
 pub mod guild;
 pub mod handlers;
 pub mod messaging;
+pub mod permissions;
 pub mod sources;
 pub mod utils;
 
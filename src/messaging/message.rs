@@ -1,8 +1,19 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    time::{Duration, SystemTime},
+};
 
-use serenity::model::mention::Mention;
+use serenity::{model::mention::Mention, prelude::Mentionable};
 
-use crate::messaging::messages::*;
+use crate::{
+    guild::{
+        schedule::Schedule,
+        settings::{DuplicatePolicy, LoopMode, PermissionLevel, TrackFallback},
+    },
+    messaging::messages::*,
+    sources::spotify::SpotifyStatus,
+    utils::{get_discord_timestamp, get_human_readable_timestamp},
+};
 
 const RELEASES_LINK: &str = "https://github.com/aquelemiguel/parrot/releases";
 
@@ -12,26 +23,128 @@ pub enum ParrotMessage {
     AutopauseOn,
     Clear,
     Error,
+    FavoriteRemoved {
+        title: String,
+    },
+    FavoritesQueued {
+        count: usize,
+        failed: usize,
+    },
     Leaving,
-    LoopDisable,
-    LoopEnable,
+    Liked {
+        title: String,
+    },
+    Loop {
+        mode: LoopMode,
+    },
+    Moved {
+        mention: Mention,
+    },
     NowPlaying,
     Pause,
     PlayAllFailed,
-    PlayDomainBanned { domain: String },
+    PlayDomainBanned {
+        domain: String,
+    },
     PlaylistQueued,
+    QuizStarted {
+        rounds: usize,
+    },
+    QuizStopped,
+    RemoveDuplicates {
+        count: usize,
+    },
     RemoveMultiple,
+    RemoveRequester {
+        mention: Mention,
+        count: usize,
+    },
+    RemoveUnavailable {
+        count: usize,
+    },
     Resume,
+    ScheduleAdded {
+        id: u32,
+        channel: Mention,
+        at: u64,
+        weekly: bool,
+    },
+    ScheduleRemoved {
+        id: u32,
+    },
+    ScheduleStarted {
+        query: String,
+    },
+    Schedules {
+        schedules: Vec<Schedule>,
+    },
     Search,
-    Seek { timestamp: String },
+    Seek {
+        timestamp: String,
+    },
+    SettingsAlwaysOn {
+        channel: Option<Mention>,
+    },
+    SettingsAnnounce {
+        channel: Option<Mention>,
+    },
+    SettingsChannels {
+        channels: Vec<Mention>,
+    },
+    SettingsDjRole {
+        role: Option<Mention>,
+    },
+    SettingsDuplicates {
+        policy: DuplicatePolicy,
+    },
+    SettingsFallback {
+        fallback: TrackFallback,
+    },
+    SettingsPermission {
+        cmd: String,
+        level: PermissionLevel,
+    },
+    SettingsStage {
+        topic: bool,
+    },
+    SettingsTimeouts {
+        idle: u64,
+        alone: u64,
+    },
+    SettingsVoteSkip {
+        threshold: u8,
+    },
     Shuffle,
     Skip,
     SkipAll,
-    SkipTo { title: String, url: String },
+    SkipTo {
+        title: String,
+        url: String,
+    },
+    SleepAfterTrack,
+    SleepCancel,
+    SleepIn {
+        at: SystemTime,
+    },
+    SpotifyReconnect,
+    SpotifyStatus {
+        status: SpotifyStatus,
+    },
     Stop,
-    Summon { mention: Mention },
-    Version { current: String },
-    VoteSkip { mention: Mention, missing: usize },
+    Summon {
+        mention: Mention,
+    },
+    Undo {
+        cmd: String,
+        failed: usize,
+    },
+    Version {
+        current: String,
+    },
+    VoteSkip {
+        mention: Mention,
+        missing: usize,
+    },
 }
 
 impl Display for ParrotMessage {
@@ -41,9 +154,27 @@ impl Display for ParrotMessage {
             Self::AutopauseOn => f.write_str(AUTOPAUSE_ON),
             Self::Clear => f.write_str(CLEARED),
             Self::Error => f.write_str(ERROR),
+            Self::FavoriteRemoved { title } => {
+                f.write_str(&format!("{} **{}**", FAVORITES_REMOVED, title))
+            }
+            Self::FavoritesQueued { count, failed } => match failed {
+                0 => f.write_str(&format!("{} **{}** track(s)!", FAVORITES_QUEUED, count)),
+                _ => f.write_str(&format!(
+                    "{} **{}** track(s), but **{}** {}",
+                    FAVORITES_QUEUED, count, failed, FAVORITES_FAILED
+                )),
+            },
             Self::Leaving => f.write_str(LEAVING),
-            Self::LoopDisable => f.write_str(LOOP_DISABLED),
-            Self::LoopEnable => f.write_str(LOOP_ENABLED),
+            Self::Liked { title } => f.write_str(&format!("{} **{}**", FAVORITES_LIKED, title)),
+            Self::Loop { mode } => match mode {
+                LoopMode::Off => f.write_str(LOOP_OFF),
+                LoopMode::Track => f.write_str(LOOP_TRACK),
+                LoopMode::Queue => f.write_str(LOOP_QUEUE),
+            },
+            Self::Moved { mention } => f.write_str(&format!(
+                "{} **{}**, the queue came along!",
+                MOVED_TO, mention
+            )),
             Self::NowPlaying => f.write_str(QUEUE_NOW_PLAYING),
             Self::Pause => f.write_str(PAUSED),
             Self::PlaylistQueued => f.write_str(PLAY_PLAYLIST),
@@ -51,9 +182,67 @@ impl Display for ParrotMessage {
             Self::PlayDomainBanned { domain } => {
                 f.write_str(&format!("⚠️ **{}** {}", domain, PLAY_FAILED_BLOCKED_DOMAIN))
             }
+            Self::QuizStarted { rounds } => {
+                f.write_str(&format!("{} **{}** round(s)...", QUIZ_STARTED, rounds))
+            }
+            Self::QuizStopped => f.write_str(QUIZ_STOPPED),
             Self::Search => f.write_str(SEARCHING),
+            Self::RemoveDuplicates { count } => f.write_str(&format!(
+                "{} **{}** {}",
+                REMOVED_COUNT, count, REMOVED_QUEUE_DUPLICATES
+            )),
             Self::RemoveMultiple => f.write_str(REMOVED_QUEUE_MULTIPLE),
+            Self::RemoveRequester { mention, count } => f.write_str(&format!(
+                "{} **{}** {} {}!",
+                REMOVED_COUNT, count, REMOVED_QUEUE_REQUESTER, mention
+            )),
+            Self::RemoveUnavailable { count } => f.write_str(&format!(
+                "{} **{}** {}",
+                REMOVED_COUNT, count, REMOVED_QUEUE_UNAVAILABLE
+            )),
             Self::Resume => f.write_str(RESUMED),
+            Self::ScheduleAdded {
+                id,
+                channel,
+                at,
+                weekly,
+            } => match weekly {
+                true => f.write_str(&format!(
+                    "{} **#{}** in {} for <t:{}:F>, {}.",
+                    SCHEDULE_ADDED, id, channel, at, SCHEDULE_WEEKLY
+                )),
+                false => f.write_str(&format!(
+                    "{} **#{}** in {} for <t:{}:F>.",
+                    SCHEDULE_ADDED, id, channel, at
+                )),
+            },
+            Self::ScheduleRemoved { id } => {
+                f.write_str(&format!("{} **#{}**!", SCHEDULE_REMOVED, id))
+            }
+            Self::ScheduleStarted { query } => {
+                f.write_str(&format!("{} `{}`!", SCHEDULE_STARTED, query))
+            }
+            Self::Schedules { schedules } => match schedules.is_empty() {
+                true => f.write_str(SCHEDULE_LIST_EMPTY),
+                false => {
+                    let lines = schedules
+                        .iter()
+                        .map(|schedule| {
+                            let weekly = if schedule.weekly { " 🔁" } else { "" };
+                            format!(
+                                "**#{}** <t:{}:F>{} in {}: `{}`",
+                                schedule.id,
+                                schedule.next_run,
+                                weekly,
+                                schedule.channel_id.mention(),
+                                schedule.query
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    f.write_str(&format!("{}\n{}", SCHEDULE_LIST, lines.join("\n")))
+                }
+            },
             Self::Shuffle => f.write_str(SHUFFLED_SUCCESS),
             Self::Stop => f.write_str(STOPPED),
             Self::VoteSkip { mention, missing } => f.write_str(&format!(
@@ -61,12 +250,105 @@ impl Display for ParrotMessage {
                 SKIP_VOTE_EMOJI, mention, SKIP_VOTE_USER, missing, SKIP_VOTE_MISSING
             )),
             Self::Seek { timestamp } => f.write_str(&format!("{} **{}**!", SEEKED, timestamp)),
+            Self::SettingsAlwaysOn { channel } => match channel {
+                Some(channel) => f.write_str(&format!("{} {}.", SETTINGS_ALWAYS_ON, channel)),
+                None => f.write_str(SETTINGS_ALWAYS_ON_OFF),
+            },
+            Self::SettingsAnnounce { channel } => match channel {
+                Some(channel) => f.write_str(&format!("{} {}.", SETTINGS_ANNOUNCE, channel)),
+                None => f.write_str(SETTINGS_ANNOUNCE_OFF),
+            },
+            Self::SettingsChannels { channels } => match channels.is_empty() {
+                true => f.write_str(SETTINGS_CHANNELS_UNBOUND),
+                false => {
+                    let channels = channels
+                        .iter()
+                        .map(|channel| channel.to_string())
+                        .collect::<Vec<_>>();
+
+                    f.write_str(&format!(
+                        "{} {}.",
+                        SETTINGS_CHANNELS_BOUND,
+                        channels.join(", ")
+                    ))
+                }
+            },
+            Self::SettingsDjRole { role } => match role {
+                Some(role) => f.write_str(&format!("{} {}.", SETTINGS_DJ_ROLE, role)),
+                None => f.write_str(SETTINGS_DJ_ROLE_CLEARED),
+            },
+            Self::SettingsDuplicates { policy } => {
+                f.write_str(&format!("{} {}.", SETTINGS_DUPLICATES, policy))
+            }
+            Self::SettingsFallback { fallback } => match fallback {
+                TrackFallback::Off => f.write_str(&format!("{} give up.", SETTINGS_FALLBACK)),
+                _ => f.write_str(&format!("{} {}.", SETTINGS_FALLBACK, fallback)),
+            },
+            Self::SettingsPermission { cmd, level } => f.write_str(&format!(
+                "{} `/{}` can be used by {}.",
+                SETTINGS_PERMISSION, cmd, level
+            )),
+            Self::SettingsStage { topic } => match topic {
+                true => f.write_str(SETTINGS_STAGE_TOPIC_ON),
+                false => f.write_str(SETTINGS_STAGE_TOPIC_OFF),
+            },
+            Self::SettingsTimeouts { idle, alone } => f.write_str(&format!(
+                "{} {} {} {}.",
+                SETTINGS_TIMEOUTS_IDLE,
+                get_human_readable_timestamp(Some(Duration::from_secs(*idle))),
+                SETTINGS_TIMEOUTS_ALONE,
+                get_human_readable_timestamp(Some(Duration::from_secs(*alone)))
+            )),
+            Self::SettingsVoteSkip { threshold } => f.write_str(&format!(
+                "{} {}% of listeners.",
+                SETTINGS_VOTESKIP, threshold
+            )),
             Self::Skip => f.write_str(SKIPPED),
             Self::SkipAll => f.write_str(SKIPPED_ALL),
             Self::SkipTo { title, url } => {
                 f.write_str(&format!("{} [**{}**]({})!", SKIPPED_TO, title, url))
             }
+            Self::SleepAfterTrack => f.write_str(SLEEP_AFTER_TRACK),
+            Self::SleepCancel => f.write_str(SLEEP_CANCELLED),
+            Self::SleepIn { at } => {
+                f.write_str(&format!("{} {}.", SLEEP_IN, get_discord_timestamp(*at)))
+            }
+            Self::SpotifyReconnect => f.write_str(SPOTIFY_RECONNECTING),
+            Self::SpotifyStatus { status } => match status {
+                SpotifyStatus::Disabled => f.write_str(SPOTIFY_STATUS_DISABLED),
+                SpotifyStatus::Connecting => f.write_str(SPOTIFY_STATUS_CONNECTING),
+                SpotifyStatus::Connected {
+                    refreshed_at,
+                    expires_at,
+                } => f.write_str(&format!(
+                    "{}\n{} {}\n{} {}",
+                    SPOTIFY_STATUS_CONNECTED,
+                    SPOTIFY_STATUS_REFRESHED,
+                    get_discord_timestamp(*refreshed_at),
+                    SPOTIFY_STATUS_EXPIRES,
+                    get_discord_timestamp(*expires_at)
+                )),
+                SpotifyStatus::Retrying {
+                    attempt,
+                    error,
+                    retry_at,
+                } => f.write_str(&format!(
+                    "{}\n`{}` (attempt {})\n{} {}",
+                    SPOTIFY_STATUS_RETRYING,
+                    error,
+                    attempt,
+                    SPOTIFY_STATUS_RETRY_AT,
+                    get_discord_timestamp(*retry_at)
+                )),
+            },
             Self::Summon { mention } => f.write_str(&format!("{} **{}**!", JOINING, mention)),
+            Self::Undo { cmd, failed } => match failed {
+                0 => f.write_str(&format!("{} `/{}`!", UNDONE, cmd)),
+                _ => f.write_str(&format!(
+                    "{} `/{}`, but **{}** {}",
+                    UNDONE, cmd, failed, UNDONE_FAILED
+                )),
+            },
             Self::Version { current } => f.write_str(&format!(
                 "{} [{}]({}/tag/v{})\n{}({}/latest)",
                 VERSION, current, RELEASES_LINK, current, VERSION_LATEST, RELEASES_LINK
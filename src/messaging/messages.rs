@@ -1,3 +1,5 @@
+pub const ALONE_ALERT: &str =
+    "Everyone left, so I'll leave too.\nFeel free to summon me back any time!";
 pub const AUTOPAUSE_OFF: &str = "🤖 Autopause OFF!";
 pub const AUTOPAUSE_ON: &str = "🤖 Autopause ON!";
 pub const CLEARED: &str = "🗑️ Cleared!";
@@ -10,58 +12,199 @@ pub const DOMAIN_FORM_TITLE: &str = "Manage sources";
 
 pub const ERROR: &str = "Fatality! Something went wrong ☹️";
 pub const FAIL_ALREADY_HERE: &str = "⚠️ I'm already here!";
+pub const FAIL_ALREADY_LIKED: &str = "⚠️ That track is already in your favorites!";
 pub const FAIL_ANOTHER_CHANNEL: &str = "⚠️ I'm already connected to";
 pub const FAIL_AUTHOR_DISCONNECTED: &str = "⚠️ You are not connected to";
 pub const FAIL_AUTHOR_NOT_FOUND: &str = "⚠️ Could not find you in any voice channel!";
+pub const FAIL_DUPLICATE_TRACK: &str = "is already in the queue!";
+pub const FAIL_FAVORITES_EMPTY: &str = "⚠️ You haven't liked any tracks yet!";
+pub const FAIL_FAVORITES_FULL: &str =
+    "⚠️ Your favorites are full! Remove a few with `/favorites remove` to make room.";
+pub const FAIL_LIKE_UNAVAILABLE: &str = "⚠️ This track can't be saved to favorites!";
 pub const FAIL_LOOP: &str = "⚠️ Failed to toggle loop!";
+pub const FAIL_MISSING_ADMIN: &str = "⚠️ Only server managers can use this command!";
+pub const FAIL_MISSING_DJ: &str =
+    "⚠️ Only DJs can use this command, unless it only affects tracks you requested!";
+pub const FAIL_MISSING_STAGE: &str =
+    "⚠️ I need permission to speak, or at least to request to speak, in that stage!";
 pub const FAIL_MINUTES_PARSING: &str = "⚠️ Invalid formatting for 'minutes'";
+pub const FAIL_NOTHING_TO_UNDO: &str = "⚠️ There's nothing to undo!";
+pub const FAIL_NO_FAVORITE_ON_INDEX: &str = "⚠️ There is no favorite on that index!";
+pub const FAIL_NO_QUIZ: &str = "⚠️ There's no quiz going on!";
+pub const FAIL_NO_SCHEDULE: &str = "⚠️ There's no schedule with that number!";
+pub const FAIL_NO_SLEEP_TIMER: &str = "⚠️ There's no sleep timer to cancel!";
 pub const FAIL_NO_SONG_ON_INDEX: &str = "⚠️ There is no queued song on that index!";
+pub const FAIL_NO_STATS: &str = "⚠️ There's nothing to show yet, play some tracks first!";
 pub const FAIL_NO_VOICE_CONNECTION: &str = "⚠️ I'm not connected to any voice channel!";
+pub const FAIL_QUIZ_BUSY: &str = "⚠️ Finish or clear the queue before starting a quiz!";
+pub const FAIL_QUIZ_IN_PROGRESS: &str = "⚠️ That can't be used while a quiz is going on!";
+pub const FAIL_QUIZ_METADATA: &str = "⚠️ Couldn't find out which track the quiz is playing!";
+pub const FAIL_QUIZ_PLAYLIST: &str = "⚠️ Quizzes need a playlist with at least 4 tracks!";
+pub const FAIL_QUIZ_RUNNING: &str = "⚠️ There's already a quiz going on!";
 pub const FAIL_REMOVE_RANGE: &str = "⚠️ `until` needs to be higher than `index`!";
+pub const FAIL_SCHEDULE_LIMIT: &str = "⚠️ This server already has as many schedules as it can!";
+pub const FAIL_SCHEDULE_TIME: &str = "⚠️ Times should look like `21:30`, in UTC!";
 pub const FAIL_SECONDS_PARSING: &str = "⚠️ Invalid formatting for 'seconds'";
+pub const FAIL_SLEEP_DURATION: &str =
+    "⚠️ Durations should look like `45m` or `1h30m`, and be a day at most!";
+pub const FAIL_UNBOUND_CHANNEL: &str = "⚠️ Music commands can only be used in";
+pub const FAIL_UNKNOWN_COMMAND: &str = "⚠️ There is no command with that name!";
+pub const FAIL_VOTE_INELIGIBLE: &str = "⚠️ Only members listening in my voice channel can vote!";
 pub const FAIL_WRONG_CHANNEL: &str = "⚠️ We are not in the same voice channel!";
+pub const FAVORITES_FAILED: &str = "track(s) couldn't be queued.";
+pub const FAVORITES_LIKED: &str = "❤️ Added to your favorites:";
+pub const FAVORITES_QUEUED: &str = "❤️ Queued";
+pub const FAVORITES_REMOVED: &str = "💔 Removed from your favorites:";
+pub const FAVORITES_TITLE: &str = "❤️ Favorites";
 pub const IDLE_ALERT: &str = "I've been idle for a while, so I'll leave for now to save resources.\nFeel free to summon me back any time!";
 pub const JOINING: &str = "Joining";
 pub const LEAVING: &str = "👋 See you soon!";
-pub const LOOP_DISABLED: &str = "🔁 Disabled loop!";
-pub const LOOP_ENABLED: &str = "🔁 Enabled loop!";
+pub const LOOP_OFF: &str = "➡️ Stopped looping!";
+pub const LOOP_QUEUE: &str = "🔁 Looping the queue!";
+pub const LOOP_TRACK: &str = "🔂 Looping the current track!";
+pub const MOVED_TO: &str = "🚚 I was moved to";
+pub const NOW_PLAYING_LIKE: &str = "Like";
+pub const NOW_PLAYING_LOOP: &str = "Loop";
+pub const NOW_PLAYING_LOOP_QUEUE: &str = "Looping queue";
+pub const NOW_PLAYING_LOOP_TRACK: &str = "Looping track";
+pub const NOW_PLAYING_PAUSE: &str = "Pause";
+pub const NOW_PLAYING_RESUME: &str = "Resume";
+pub const NOW_PLAYING_SHUFFLE: &str = "Shuffle";
+pub const NOW_PLAYING_SKIP: &str = "Skip";
+pub const NOW_PLAYING_STOP: &str = "Stop";
 pub const NOTHING_IS_PLAYING: &str = "🔈 Nothing is playing!";
 pub const PAUSED: &str = "⏸️ Paused!";
 pub const PLAY_FAILED_BLOCKED_DOMAIN: &str = "**is either not allowed in this server or is not supported!** \n\nTo explicitely allow this domain, ask a moderator to run the `/managesources` command. [Click to see a list of supported sources.](https://github.com/yt-dlp/yt-dlp/blob/master/supportedsites.md)";
 pub const PLAY_ALL_FAILED: &str =
     "⚠️ Cannot fetch playlist via keywords! Try passing this command an URL.";
+pub const PLAY_DUPLICATES_MORE: &str = "more track(s) were already queued too.";
+pub const PLAY_DUPLICATES_QUEUED: &str = "⚠️ Some tracks were already in the queue";
+pub const PLAY_DUPLICATES_REFUSED: &str = "⚠️ Skipped tracks that were already in the queue";
+pub const PLAY_FAILED_TRACKS: &str = "⚠️ Some tracks couldn't be queued";
+pub const PLAY_FAILED_TRACKS_MORE: &str = "more track(s) couldn't be queued either.";
 pub const PLAY_PLAYLIST: &str = "📃 Added playlist to queue!";
 pub const PLAY_QUEUE: &str = "📃 Added to queue!";
 pub const PLAY_TOP: &str = "📃 Added to top!";
 pub const QUEUE_EXPIRED: &str =
     "In order to save resources, this command has expired.\nPlease feel free to reinvoke it!";
+pub const QUEUE_FIND_JUMP: &str = "⏭️ Jump to";
+pub const QUEUE_FIND_NONE: &str = "🔍 Nothing in the queue matches";
+pub const QUEUE_FIND_PLAYING: &str = "▶️";
+pub const QUEUE_FIND_REMOVE: &str = "🗑️ Remove";
+pub const QUEUE_FIND_RESULTS: &str = "🔍 Found in the queue";
 pub const QUEUE_IS_EMPTY: &str = "Queue is empty!";
 pub const QUEUE_NO_SONGS: &str = "There's no songs up next!";
 pub const QUEUE_NOTHING_IS_PLAYING: &str = "Nothing is playing!";
 pub const QUEUE_NOW_PLAYING: &str = "🔊 Now playing";
 pub const QUEUE_PAGE_OF: &str = "of";
 pub const QUEUE_PAGE: &str = "Page";
+pub const QUEUE_ACTION_NEXT: &str = "Play next";
+pub const QUEUE_ACTION_PLAYING_NEXT: &str = "⏭️ Playing next:";
+pub const QUEUE_ACTION_MOVED_TOP: &str = "🔝 Moved to the top:";
+pub const QUEUE_ACTION_REMOVE: &str = "Remove";
+pub const QUEUE_ACTION_TOP: &str = "Move to top";
+pub const QUEUE_DETAILS_CHANNEL: &str = "Channel";
+pub const QUEUE_DETAILS_DURATION: &str = "Duration";
+pub const QUEUE_DETAILS_POSITION: &str = "Position";
+pub const QUEUE_DETAILS_REQUESTER: &str = "Requested by";
+pub const QUEUE_SELECT_PLACEHOLDER: &str = "Pick a track for more options";
+pub const QUEUE_STATUS_AUTOPAUSE: &str = "⏯️ Autopause";
+pub const QUEUE_STATUS_QUEUE_LOOP: &str = "🔁 Queue loop";
+pub const QUEUE_STATUS_TRACK_LOOP: &str = "🔂 Track loop";
+pub const QUEUE_SUMMARY_REMAINING: &str = "remaining";
+pub const QUEUE_SUMMARY_TRACK: &str = "track";
+pub const QUEUE_SUMMARY_TRACKS: &str = "tracks";
+pub const QUEUE_TRACK_GONE: &str = "⚠️ That track isn't in the queue anymore!";
+pub const QUEUE_TRACK_PLAYING: &str = "⚠️ That track is already playing!";
 pub const QUEUE_UP_NEXT: &str = "⌛ Up next";
+pub const QUIZ_ALREADY_ANSWERED: &str = "⚠️ You've already had a go at this round!";
+pub const QUIZ_ANSWER: &str = "It was";
+pub const QUIZ_CORRECT: &str = "got it!";
+pub const QUIZ_FAILED: &str = "⚠️ None of the quiz's tracks could be loaded!";
+pub const QUIZ_NO_SCORES: &str = "Nobody got a single one right!";
+pub const QUIZ_ROUND: &str = "🎵 Round";
+pub const QUIZ_ROUND_OF: &str = "of";
+pub const QUIZ_ROUND_PROMPT: &str = "What's playing? Pick an answer below!";
+pub const QUIZ_ROUND_PROMPT_CHAT: &str = "What's playing? Pick an answer below or type it in chat!";
+pub const QUIZ_SCOREBOARD: &str = "🏆 Scoreboard";
+pub const QUIZ_STARTED: &str = "🎲 Quiz time! Getting ready";
+pub const QUIZ_STOPPED: &str = "🛑 Stopped the quiz!";
+pub const QUIZ_TIMES_UP: &str = "⏰ Time's up!";
+pub const QUIZ_WRONG: &str = "❌ Not quite!";
+pub const RECONNECT_FAILED: &str =
+    "⚠️ **Lost my voice connection!**\nI couldn't reconnect, so feel free to summon me back.";
+pub const REMOVED_COUNT: &str = "❌ Removed";
+pub const REMOVED_QUEUE_DUPLICATES: &str = "repeated track(s) from queue!";
 pub const REMOVED_QUEUE_MULTIPLE: &str = "❌ Removed multiple tracks from queue!";
 pub const REMOVED_QUEUE: &str = "❌ Removed from queue";
 pub const RESUMED: &str = "▶️ Resumed!";
+pub const SCHEDULE_ADDED: &str = "⏰ Scheduled";
+pub const SCHEDULE_LIST: &str = "⏰ **Scheduled playback**";
+pub const SCHEDULE_LIST_EMPTY: &str = "⏰ Nothing is scheduled.";
+pub const SCHEDULE_REMOVED: &str = "🗑️ Removed schedule";
+pub const SCHEDULE_STARTED: &str = "⏰ Starting scheduled playback of";
+pub const SCHEDULE_WEEKLY: &str = "and every week after that";
 pub const SEARCHING: &str = "🔎 Searching...";
 pub const SEEKED: &str = "⏩ Seeked current track to";
+pub const SETTINGS_ALWAYS_ON_OFF: &str = "📻 24/7 mode is now off.";
+pub const SETTINGS_ALWAYS_ON: &str = "📻 24/7 mode is now on, I'll stay in";
+pub const SETTINGS_ANNOUNCE_OFF: &str = "📣 I'll no longer announce tracks as they start.";
+pub const SETTINGS_ANNOUNCE: &str = "📣 I'll now announce every track as it starts in";
+pub const SETTINGS_CHANNELS_BOUND: &str = "💬 Music commands can now only be used in";
+pub const SETTINGS_CHANNELS_UNBOUND: &str = "💬 Music commands can be used in any channel.";
+pub const SETTINGS_DJ_ROLE: &str = "🎧 DJ commands are now limited to";
+pub const SETTINGS_DJ_ROLE_CLEARED: &str = "🎧 DJ commands are now open to everyone.";
+pub const SETTINGS_DUPLICATES: &str = "🪞 When a track is already in the queue, I'll now";
+pub const SETTINGS_FALLBACK: &str = "🛟 When a track can't be loaded, I'll now";
+pub const SETTINGS_PERMISSION: &str = "🔐 From now on,";
+pub const SETTINGS_STAGE_TOPIC_OFF: &str = "🎙️ I'll leave stage topics alone.";
+pub const SETTINGS_STAGE_TOPIC_ON: &str = "🎙️ Stage topics will now follow the current track.";
+pub const SETTINGS_VOTESKIP: &str = "🗳 Skipping a track now takes the votes of";
+pub const SETTINGS_TIMEOUTS_ALONE: &str = "or once I've been alone for";
+pub const SETTINGS_TIMEOUTS_IDLE: &str = "⏱️ I'll now leave after being idle for";
 pub const SHUFFLED_SUCCESS: &str = "🔀 Shuffled successfully!";
+pub const SKIP_VOTE_BUTTON: &str = "Vote skip";
 pub const SKIP_VOTE_EMOJI: &str = "🗳 ";
+pub const SKIP_VOTE_EXPIRED: &str = "🗳 This vote is over!";
 pub const SKIP_VOTE_MISSING: &str = "more vote(s) needed to skip!";
+pub const SKIP_VOTE_TALLY: &str = "votes";
 pub const SKIP_VOTE_USER: &str = "has voted to skip!";
 pub const SKIPPED_ALL: &str = "⏭️ Skipped until infinity!";
 pub const SKIPPED_TO: &str = "⏭️ Skipped to";
 pub const SKIPPED: &str = "⏭️ Skipped!";
+pub const SLEEP_AFTER_TRACK: &str = "😴 I'll stop and leave once this track is over.";
+pub const SLEEP_ALERT: &str = "😴 Time's up, so I stopped the music and left.\nGood night!";
+pub const SLEEP_CANCELLED: &str = "⏰ Cancelled the sleep timer.";
+pub const SLEEP_IN: &str = "😴 I'll stop and leave";
+pub const SOURCE_FETCH_FAILED: &str = "⚠️ **Failed to fetch that link!**\nThe service might be unavailable or the content might be private.";
+pub const SOURCE_INVALID_QUERY: &str = "⚠️ **Could not find any tracks with that link!**\nAre you sure it points to a track, album or playlist?";
 pub const SPOTIFY_AUTH_FAILED: &str = "⚠️ **Could not authenticate with Spotify!**\nDid you forget to provide your Spotify application's client ID and secret?";
+pub const SPOTIFY_RECONNECTING: &str = "🔄 Reconnecting to Spotify...";
+pub const SPOTIFY_STATUS_CONNECTED: &str = "🟢 **Connected to Spotify!**";
+pub const SPOTIFY_STATUS_CONNECTING: &str = "🟡 **Connecting to Spotify...**";
+pub const SPOTIFY_STATUS_DISABLED: &str =
+    "⚫ **Spotify is disabled!**\nNo client ID and secret were provided.";
+pub const SPOTIFY_STATUS_EXPIRES: &str = "Token expires";
+pub const SPOTIFY_STATUS_REFRESHED: &str = "Token refreshed";
+pub const SPOTIFY_STATUS_RETRYING: &str = "🔴 **Could not authenticate with Spotify!**";
+pub const SPOTIFY_STATUS_RETRY_AT: &str = "Retrying";
 pub const SPOTIFY_INVALID_QUERY: &str =
     "⚠️ **Could not find any tracks with that link!**\nAre you sure that is a valid Spotify URL?";
 pub const SPOTIFY_PLAYLIST_FAILED: &str = "⚠️ **Failed to fetch playlist!**\nIt's likely that this playlist is either private or a personalized recommendation playlist generated by Spotify.";
+pub const STATS_BUSIEST: &str = "📈 Busiest times";
+pub const STATS_LISTENED: &str = "of listening over the last";
+pub const STATS_TITLE: &str = "📊 Listening stats";
+pub const STATS_TITLE_ME: &str = "📊 Listening stats for";
+pub const STATS_TOP_REQUESTERS: &str = "🙋 Top requesters";
+pub const STATS_TOP_TRACKS: &str = "🎵 Most played";
 pub const STOPPED: &str = "⏹️ Stopped!";
 pub const TRACK_DURATION: &str = "Track duration: ";
 pub const TRACK_NOT_FOUND: &str = "⚠️ **Could not play track!**\nYour request yielded no results.";
+pub const TRACK_ERROR_SKIPPED: &str = "⏭️ Skipped";
+pub const TRACK_ERROR_UNKNOWN: &str = "a track that stopped playing";
 pub const TRACK_INAPPROPRIATE: &str = "⚠️ **Could not play track!**\nThe video you requested may be inappropriate for some users, so sign-in is required.";
 pub const TRACK_TIME_TO_PLAY: &str = "Estimated time until play: ";
+pub const UNDONE: &str = "↩️ Undid";
+pub const UNDONE_FAILED: &str = "track(s) couldn't be queued again.";
 pub const VERSION_LATEST: &str = "Find the latest version [here]";
 pub const VERSION: &str = "Version";
@@ -0,0 +1,196 @@
+use serenity::{
+    all::{
+        ChannelId, CommandInteraction, ComponentInteraction, GuildId, Member, Permissions, UserId,
+    },
+    client::Context,
+    prelude::Mentionable,
+};
+
+use crate::{
+    connection::{check_command_connection, check_voice_connections},
+    errors::{ParrotError, verify},
+    guild::{
+        metadata_store::MetadataStore,
+        settings::{GuildSettingsMap, PermissionLevel},
+    },
+};
+
+/// The level each command requires unless a guild says otherwise.
+/// Returns [`None`] for commands that don't exist.
+pub fn default_permission_level(command: &str) -> Option<PermissionLevel> {
+    match command {
+        "favorites" | "like" | "np" | "play" | "queue" | "stats" | "summon" | "version"
+        | "voteskip" => Some(PermissionLevel::Everyone),
+        "autopause" | "clear" | "leave" | "loop" | "pause" | "remove" | "resume" | "seek"
+        | "quiz" | "shuffle" | "skip" | "sleep" | "stop" | "superplay" | "undo" => {
+            Some(PermissionLevel::Dj)
+        }
+        "managesources" | "schedule" | "settings" | "spotify" => Some(PermissionLevel::Admin),
+        _ => None,
+    }
+}
+
+pub async fn check_permissions(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let command_name = interaction.data.name.as_str();
+    let member = interaction.member.as_deref();
+
+    match check_member_permissions(ctx, guild_id, command_name, member).await {
+        Err(_) if owns_affected_tracks(ctx, interaction).await => Ok(()),
+        result => result,
+    }
+}
+
+/// Checks whether a member may use a command, however they're using it.
+pub async fn check_member_permissions(
+    ctx: &Context,
+    guild_id: GuildId,
+    command_name: &str,
+    member: Option<&Member>,
+) -> Result<(), ParrotError> {
+    let (required, dj_role) = {
+        let data = ctx.data.read().await;
+        match data.get::<GuildSettingsMap>().unwrap().get(&guild_id) {
+            Some(guild_settings) => (
+                guild_settings.get_permission_level(command_name),
+                guild_settings.dj_role,
+            ),
+            None => (
+                default_permission_level(command_name).unwrap_or(PermissionLevel::Everyone),
+                None,
+            ),
+        }
+    };
+
+    let permissions = member
+        .and_then(|member| member.permissions)
+        .unwrap_or(Permissions::empty());
+
+    let is_admin = permissions.administrator() || permissions.manage_guild();
+
+    // without a DJ role, DJ commands are open to everyone just like before
+    let is_dj = is_admin
+        || dj_role.is_none_or(|role| member.is_some_and(|member| member.roles.contains(&role)));
+
+    let is_allowed = match required {
+        PermissionLevel::Everyone => true,
+        PermissionLevel::Dj => is_dj,
+        PermissionLevel::Admin => is_admin,
+    };
+
+    match is_allowed {
+        true => Ok(()),
+        false => Err(ParrotError::MissingPermission(required)),
+    }
+}
+
+/// Puts a component through the same checks as the command it stands in for.
+/// Members who requested every track in `owned` pass whatever the command's level.
+pub async fn check_component_permissions(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    command_name: &str,
+    owned: Option<(usize, usize)>,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    check_bound_channel(ctx, guild_id, interaction.channel_id).await?;
+
+    let connection = {
+        let guild = ctx.cache.guild(guild_id).unwrap();
+        let bot_id = ctx.cache.current_user().id;
+        check_voice_connections(&guild, &interaction.user.id, &bot_id)
+    };
+    check_command_connection(command_name, connection)?;
+
+    let member = interaction.member.as_ref();
+    if let Err(err) = check_member_permissions(ctx, guild_id, command_name, member).await {
+        let owns_tracks = match owned {
+            Some((from, until)) => {
+                owns_tracks(ctx, guild_id, interaction.user.id, from, until).await
+            }
+            None => false,
+        };
+        verify(owns_tracks, err)?;
+    }
+
+    Ok(())
+}
+
+/// Rejects commands sent outside of the guild's bound text channels, if it has any.
+pub async fn check_bound_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<(), ParrotError> {
+    let data = ctx.data.read().await;
+    let Some(guild_settings) = data.get::<GuildSettingsMap>().unwrap().get(&guild_id) else {
+        return Ok(());
+    };
+
+    if guild_settings.is_channel_bound(channel_id) {
+        return Ok(());
+    }
+
+    let mentions = guild_settings
+        .bound_channels
+        .iter()
+        .map(|channel_id| channel_id.mention())
+        .collect();
+
+    Err(ParrotError::UnboundChannel(mentions))
+}
+
+/// Requesters may always skip or remove their own tracks, whatever the command's level.
+async fn owns_affected_tracks(ctx: &Context, interaction: &CommandInteraction) -> bool {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let args = &interaction.data.options;
+    let get_index = |idx: usize| {
+        args.get(idx)
+            .and_then(|arg| arg.value.as_i64())
+            .map(|value| value.max(0) as usize)
+    };
+
+    // same ranges as the commands themselves, where 0 is the current track
+    let (from, until) = match interaction.data.name.as_str() {
+        "skip" => (0, get_index(0).unwrap_or(1)),
+        "remove" => {
+            let from = get_index(0).unwrap_or(0);
+            (from, get_index(1).unwrap_or(from) + 1)
+        }
+        _ => return false,
+    };
+
+    owns_tracks(ctx, guild_id, interaction.user.id, from, until).await
+}
+
+/// Whether every track in the given range of the queue was requested by the user.
+pub async fn owns_tracks(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    from: usize,
+    until: usize,
+) -> bool {
+    let manager = songbird::get(ctx).await.unwrap();
+    let Some(call) = manager.get(guild_id) else {
+        return false;
+    };
+
+    let queue = call.lock().await.queue().current_queue();
+    let until = until.min(queue.len());
+
+    if from >= until {
+        return false;
+    }
+
+    let data = ctx.data.read().await;
+    let metadata_store = data.get::<MetadataStore>().unwrap();
+
+    queue[from..until]
+        .iter()
+        .all(|track| metadata_store.retrieve_requester(&track.uuid().to_string()) == Some(user_id))
+}
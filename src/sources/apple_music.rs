@@ -0,0 +1,155 @@
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    messaging::messages::{SOURCE_FETCH_FAILED, SOURCE_INVALID_QUERY},
+    sources::{
+        Source, SourceMetadata, build_keywords,
+        page::{extract_meta_contents, fetch_text},
+    },
+};
+use serde_json::Value;
+use serenity::async_trait;
+use url::Url;
+
+const ITUNES_LOOKUP_URL: &str = "https://itunes.apple.com/lookup";
+const DEFAULT_STOREFRONT: &str = "us";
+
+#[derive(Debug, PartialEq)]
+pub enum AppleMusicLink {
+    Song(String),
+    Album(String),
+    Playlist,
+}
+
+pub struct AppleMusic;
+
+impl AppleMusic {
+    pub fn parse_link(url: &Url) -> Option<AppleMusicLink> {
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        // links usually start with a two-letter storefront, e.g. /us/album/...
+        let segments = match segments.first() {
+            Some(storefront) if storefront.len() == 2 => &segments[1..],
+            _ => &segments[..],
+        };
+
+        let kind = *segments.first()?;
+        let id = segments.last()?.to_string();
+
+        // a single song is shared as its album link plus the track ID in the `i` parameter
+        let track_id = url
+            .query_pairs()
+            .find(|(key, _)| key == "i")
+            .map(|(_, value)| value.to_string());
+
+        match (kind, track_id) {
+            ("album", Some(track_id)) => Some(AppleMusicLink::Song(track_id)),
+            ("album", None) => Some(AppleMusicLink::Album(id)),
+            ("song", _) => Some(AppleMusicLink::Song(id)),
+            ("playlist", _) => Some(AppleMusicLink::Playlist),
+            _ => None,
+        }
+    }
+
+    /// Extracts `artist - title` keywords for every song in an iTunes lookup response.
+    pub fn parse_lookup(value: &Value) -> Vec<String> {
+        let Some(results) = value.get("results").and_then(Value::as_array) else {
+            return Vec::new();
+        };
+
+        results
+            .iter()
+            .filter(|result| result.get("wrapperType").and_then(Value::as_str) == Some("track"))
+            .filter_map(|result| {
+                let title = result.get("trackName")?.as_str()?;
+                let artist = result.get("artistName").and_then(Value::as_str);
+                Some(build_keywords(artist, title))
+            })
+            .collect()
+    }
+
+    /// Playlists aren't exposed through the public lookup API, but their pages
+    /// list every song link in `music:song` meta tags.
+    pub fn parse_playlist_page(html: &str) -> Vec<String> {
+        extract_meta_contents(html, "music:song")
+            .iter()
+            .filter_map(
+                |song| match Url::parse(song).ok().map(|url| Self::parse_link(&url)) {
+                    Some(Some(AppleMusicLink::Song(id))) => Some(id),
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
+    fn get_storefront(url: &Url) -> String {
+        url.path_segments()
+            .and_then(|mut segments| segments.next())
+            .filter(|storefront| storefront.len() == 2)
+            .unwrap_or(DEFAULT_STOREFRONT)
+            .to_string()
+    }
+
+    async fn lookup(
+        http_client: &reqwest::Client,
+        ids: &[String],
+        storefront: &str,
+    ) -> Result<Vec<String>, ParrotError> {
+        let body = http_client
+            .get(ITUNES_LOOKUP_URL)
+            .query(&[
+                ("id", ids.join(",").as_str()),
+                ("entity", "song"),
+                ("country", storefront),
+                ("limit", "200"),
+            ])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))?
+            .text()
+            .await
+            .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))?;
+
+        let value: Value = serde_json::from_str(&body)?;
+        Ok(Self::parse_lookup(&value))
+    }
+}
+
+#[async_trait]
+impl Source for AppleMusic {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "Apple Music",
+            streams_from_origin: false,
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        url.host_str() == Some("music.apple.com")
+    }
+
+    async fn resolve(
+        &self,
+        http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        let link = Self::parse_link(url).ok_or(ParrotError::Other(SOURCE_INVALID_QUERY))?;
+        let storefront = Self::get_storefront(url);
+
+        let ids = match link {
+            AppleMusicLink::Song(id) | AppleMusicLink::Album(id) => vec![id],
+            AppleMusicLink::Playlist => {
+                let html = fetch_text(http_client, url.as_str()).await?;
+                Self::parse_playlist_page(&html)
+            }
+        };
+
+        if ids.is_empty() {
+            return Err(ParrotError::Other(SOURCE_INVALID_QUERY));
+        }
+
+        let keywords = Self::lookup(http_client, &ids, &storefront).await?;
+        Ok(keywords.into_iter().map(QueryType::Keywords).collect())
+    }
+}
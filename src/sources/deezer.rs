@@ -0,0 +1,135 @@
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    messaging::messages::{SOURCE_FETCH_FAILED, SOURCE_INVALID_QUERY},
+    sources::{Source, SourceMetadata, build_keywords, page::fetch_text},
+};
+use serde_json::Value;
+use serenity::async_trait;
+use std::str::FromStr;
+use url::Url;
+
+const DEEZER_API_URL: &str = "https://api.deezer.com";
+const DEEZER_SHORT_LINK_HOSTS: [&str; 2] = ["deezer.page.link", "link.deezer.com"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeezerMediaType {
+    Track,
+    Album,
+    Playlist,
+}
+
+impl FromStr for DeezerMediaType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "track" => Ok(Self::Track),
+            "album" => Ok(Self::Album),
+            "playlist" => Ok(Self::Playlist),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Deezer;
+
+impl Deezer {
+    pub fn parse_link(url: &Url) -> Option<(DeezerMediaType, String)> {
+        let mut segments = url.path_segments()?.filter(|s| !s.is_empty()).peekable();
+
+        // skip the optional language prefix, e.g. /en/track/...
+        if segments
+            .peek()
+            .is_some_and(|s| DeezerMediaType::from_str(s).is_err())
+        {
+            segments.next();
+        }
+
+        let media_type = DeezerMediaType::from_str(segments.next()?).ok()?;
+        let id = segments.next()?;
+
+        id.chars()
+            .all(|c| c.is_ascii_digit())
+            .then(|| (media_type, id.to_string()))
+    }
+
+    /// Extracts `artist - title` keywords from a Deezer API track, album or playlist object.
+    pub fn parse_tracks(media_type: DeezerMediaType, value: &Value) -> Vec<String> {
+        let to_keywords = |track: &Value| {
+            let title = track.get("title")?.as_str()?;
+            let artist = track.pointer("/artist/name").and_then(Value::as_str);
+            Some(build_keywords(artist, title))
+        };
+
+        match media_type {
+            DeezerMediaType::Track => to_keywords(value).into_iter().collect(),
+            DeezerMediaType::Album | DeezerMediaType::Playlist => value
+                .pointer("/tracks/data")
+                .and_then(Value::as_array)
+                .map(|tracks| tracks.iter().filter_map(to_keywords).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Short links only redirect to the actual page, so follow them to find out what they point to.
+    async fn expand_short_link(
+        http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Url, ParrotError> {
+        let response = http_client
+            .get(url.as_str())
+            .send()
+            .await
+            .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))?;
+
+        Ok(response.url().clone())
+    }
+}
+
+#[async_trait]
+impl Source for Deezer {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "Deezer",
+            streams_from_origin: false,
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        matches!(
+            url.host_str(),
+            Some("deezer.com" | "www.deezer.com" | "deezer.page.link" | "link.deezer.com")
+        )
+    }
+
+    async fn resolve(
+        &self,
+        http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        let url = match url.host_str() {
+            Some(host) if DEEZER_SHORT_LINK_HOSTS.contains(&host) => {
+                Self::expand_short_link(http_client, url).await?
+            }
+            _ => url.clone(),
+        };
+
+        let (media_type, id) =
+            Self::parse_link(&url).ok_or(ParrotError::Other(SOURCE_INVALID_QUERY))?;
+
+        let endpoint = match media_type {
+            DeezerMediaType::Track => format!("{}/track/{}", DEEZER_API_URL, id),
+            DeezerMediaType::Album => format!("{}/album/{}", DEEZER_API_URL, id),
+            DeezerMediaType::Playlist => format!("{}/playlist/{}", DEEZER_API_URL, id),
+        };
+
+        let body = fetch_text(http_client, &endpoint).await?;
+        let value: Value = serde_json::from_str(&body)?;
+
+        Ok(Self::parse_tracks(media_type, &value)
+            .into_iter()
+            .map(QueryType::Keywords)
+            .collect())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    sources::{Source, SourceMetadata},
+};
+use serenity::async_trait;
+use songbird::input::AuxMetadata;
+use url::Url;
+
+const AUDIO_EXTENSIONS: [&str; 9] = [
+    "aac", "alac", "flac", "m4a", "mp3", "mp4", "ogg", "opus", "wav",
+];
+
+/// Audio files served over plain HTTP, which don't need yt-dlp to be streamed.
+pub struct DirectLink;
+
+impl DirectLink {
+    fn file_name(url: &Url) -> Option<&str> {
+        url.path_segments()?
+            .next_back()
+            .filter(|name| !name.is_empty())
+    }
+
+    /// There's nothing to extract metadata from besides the link itself.
+    pub fn build_metadata(url: &str) -> AuxMetadata {
+        let title = Url::parse(url)
+            .ok()
+            .and_then(|url| Self::file_name(&url).map(|name| name.to_string()))
+            .unwrap_or_else(|| url.to_string());
+
+        AuxMetadata {
+            title: Some(title),
+            source_url: Some(url.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl Source for DirectLink {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "HTTP",
+            streams_from_origin: true,
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let extension = Self::file_name(url)
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+
+        matches!(url.scheme(), "http" | "https")
+            && extension.is_some_and(|extension| AUDIO_EXTENSIONS.contains(&extension.as_str()))
+    }
+
+    async fn resolve(
+        &self,
+        _http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        Ok(vec![QueryType::DirectLink(url.to_string())])
+    }
+}
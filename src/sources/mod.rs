@@ -1,3 +1,89 @@
-// pub mod ffmpeg;
+pub mod apple_music;
+pub mod deezer;
+pub mod direct;
+pub mod page;
+pub mod soundcloud;
 pub mod spotify;
-// pub mod youtube;
+pub mod tidal;
+pub mod youtube;
+
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    sources::{
+        apple_music::AppleMusic, deezer::Deezer, direct::DirectLink, soundcloud::SoundCloud,
+        spotify::Spotify, tidal::Tidal, youtube::YouTube,
+    },
+};
+use serenity::{async_trait, prelude::TypeMapKey};
+use std::sync::Arc;
+use url::Url;
+
+/// Describes a source independently of any link it resolves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceMetadata {
+    pub name: &'static str,
+    /// Whether the resolved tracks are streamed from the link's own domain,
+    /// in which case the guild's allowed and banned domains apply to it.
+    pub streams_from_origin: bool,
+}
+
+/// A streaming service whose links can be turned into queries Parrot knows how to play.
+#[async_trait]
+pub trait Source: Send + Sync {
+    fn metadata(&self) -> SourceMetadata;
+
+    /// Whether this source knows how to handle the given link.
+    fn matches(&self, url: &Url) -> bool;
+
+    /// Turns a link into one query per track it refers to.
+    async fn resolve(
+        &self,
+        http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError>;
+}
+
+/// The ordered list of sources links are matched against, first match wins.
+#[derive(Default)]
+pub struct SourceRegistry {
+    sources: Vec<Arc<dyn Source>>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+            .register(Spotify {})
+            .register(AppleMusic)
+            .register(Deezer)
+            .register(Tidal)
+            .register(SoundCloud)
+            .register(DirectLink)
+            // yt-dlp supports most sites, so it must come last
+            .register(YouTube)
+    }
+
+    pub fn register<S: Source + 'static>(mut self, source: S) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    pub fn find(&self, url: &Url) -> Option<Arc<dyn Source>> {
+        self.sources
+            .iter()
+            .find(|source| source.matches(url))
+            .cloned()
+    }
+}
+
+impl TypeMapKey for SourceRegistry {
+    type Value = Arc<SourceRegistry>;
+}
+
+/// Builds a search query in the same shape Spotify tracks are looked up with.
+pub fn build_keywords(artist: Option<&str>, title: &str) -> String {
+    match artist {
+        Some(artist) if !artist.is_empty() => format!("{} - {}", artist, title),
+        _ => title.to_string(),
+    }
+}
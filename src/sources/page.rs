@@ -0,0 +1,78 @@
+use crate::{errors::ParrotError, messaging::messages::SOURCE_FETCH_FAILED};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+
+lazy_static! {
+    static ref META_TAG_REGEX: Regex = Regex::new(r"<meta\s[^>]*>").unwrap();
+    static ref ATTRIBUTE_REGEX: Regex =
+        Regex::new(r#"(?P<name>[\w:-]+)\s*=\s*"(?P<value>[^"]*)""#).unwrap();
+}
+
+/// The subset of an oEmbed response that is useful to build a search query.
+#[derive(Debug, Default, Deserialize)]
+pub struct OEmbed {
+    pub title: Option<String>,
+    pub author_name: Option<String>,
+}
+
+pub async fn fetch_text(http_client: &reqwest::Client, url: &str) -> Result<String, ParrotError> {
+    http_client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))?
+        .text()
+        .await
+        .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))
+}
+
+pub async fn fetch_oembed(
+    http_client: &reqwest::Client,
+    endpoint: &str,
+    url: &str,
+) -> Result<OEmbed, ParrotError> {
+    let body = http_client
+        .get(endpoint)
+        .query(&[("url", url), ("format", "json")])
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))?
+        .text()
+        .await
+        .map_err(|_| ParrotError::Other(SOURCE_FETCH_FAILED))?;
+
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Returns the `content` of every `<meta>` tag whose `property` (or `name`) matches.
+pub fn extract_meta_contents(html: &str, property: &str) -> Vec<String> {
+    META_TAG_REGEX
+        .find_iter(html)
+        .filter_map(|tag| {
+            let mut matches_property = false;
+            let mut content = None;
+
+            for attribute in ATTRIBUTE_REGEX.captures_iter(tag.as_str()) {
+                match &attribute["name"] {
+                    "property" | "name" => matches_property |= &attribute["value"] == property,
+                    "content" => content = Some(decode_entities(&attribute["value"])),
+                    _ => {}
+                }
+            }
+
+            content.filter(|_| matches_property)
+        })
+        .collect()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,69 @@
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    messaging::messages::SOURCE_INVALID_QUERY,
+    sources::{Source, SourceMetadata},
+};
+use serenity::async_trait;
+use url::Url;
+
+/// Profile tabs that list several tracks instead of a single one.
+const COLLECTION_TABS: [&str; 6] = [
+    "sets",
+    "tracks",
+    "albums",
+    "likes",
+    "reposts",
+    "popular-tracks",
+];
+
+pub struct SoundCloud;
+
+impl SoundCloud {
+    /// SoundCloud is streamed by yt-dlp directly, so links only need to be told apart
+    /// between single tracks and collections.
+    pub fn classify(url: &Url) -> Option<QueryType> {
+        if url.host_str() == Some("on.soundcloud.com") {
+            return Some(QueryType::VideoLink(url.to_string()));
+        }
+
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        match segments.as_slice() {
+            [] => None,
+            [_user] => Some(QueryType::PlaylistLink(url.to_string())),
+            [_user, tab, ..] if COLLECTION_TABS.contains(tab) => {
+                Some(QueryType::PlaylistLink(url.to_string()))
+            }
+            [_user, _track, ..] => Some(QueryType::VideoLink(url.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl Source for SoundCloud {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "SoundCloud",
+            streams_from_origin: true,
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        matches!(
+            url.host_str(),
+            Some(
+                "soundcloud.com" | "www.soundcloud.com" | "m.soundcloud.com" | "on.soundcloud.com"
+            )
+        )
+    }
+
+    async fn resolve(
+        &self,
+        _http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        let query = Self::classify(url).ok_or(ParrotError::Other(SOURCE_INVALID_QUERY))?;
+        Ok(vec![query])
+    }
+}
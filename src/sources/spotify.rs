@@ -1,7 +1,8 @@
 use crate::{
     commands::play::QueryType,
-    errors::ParrotError,
-    messaging::messages::{SPOTIFY_INVALID_QUERY, SPOTIFY_PLAYLIST_FAILED},
+    errors::{ParrotError, verify},
+    messaging::messages::{SPOTIFY_AUTH_FAILED, SPOTIFY_INVALID_QUERY, SPOTIFY_PLAYLIST_FAILED},
+    sources::{Source, SourceMetadata},
 };
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -10,17 +11,37 @@ use rspotify::{
     clients::BaseClient,
     model::{AlbumId, PlayableItem, PlaylistId, SimplifiedArtist, TrackId},
 };
-use std::{env, str::FromStr};
-use tokio::sync::Mutex;
+use serenity::async_trait;
+use std::{
+    cmp::min,
+    env,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    sync::{Mutex, Notify},
+    time::sleep,
+};
+use url::Url;
+
+const AUTH_RETRY_DELAY: Duration = Duration::from_secs(5);
+const AUTH_RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 10);
+const TOKEN_DEFAULT_LIFETIME: Duration = Duration::from_secs(60 * 60);
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60 * 5);
+
+static SPOTIFY_MANAGER_STARTED: AtomicBool = AtomicBool::new(false);
 
 lazy_static! {
     pub static ref SPOTIFY: Mutex<Result<ClientCredsSpotify, ParrotError>> =
         Mutex::new(Err(ParrotError::Other("no auth attempts")));
+    pub static ref SPOTIFY_STATUS: Mutex<SpotifyStatus> = Mutex::new(SpotifyStatus::Connecting);
+    static ref SPOTIFY_RECONNECT: Notify = Notify::new();
     pub static ref SPOTIFY_QUERY_REGEX: Regex =
         Regex::new(r"spotify.com/(?P<media_type>.+)/(?P<media_id>.*?)(?:\?|$)").unwrap();
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MediaType {
     Track,
     Album,
@@ -40,9 +61,121 @@ impl FromStr for MediaType {
     }
 }
 
+/// The state of the managed Spotify client, as reported by `/spotify status`.
+#[derive(Clone, Debug)]
+pub enum SpotifyStatus {
+    Disabled,
+    Connecting,
+    Connected {
+        refreshed_at: SystemTime,
+        expires_at: SystemTime,
+    },
+    Retrying {
+        attempt: u32,
+        error: String,
+        retry_at: SystemTime,
+    },
+}
+
 pub struct Spotify {}
 
 impl Spotify {
+    /// Starts the background task that keeps [`SPOTIFY`] authenticated.
+    /// Calling this more than once is a no-op, since `ready` fires again on every gateway reconnect.
+    pub fn spawn_manager() {
+        if SPOTIFY_MANAGER_STARTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        tokio::spawn(Self::manage());
+    }
+
+    /// Wakes the manager up so it authenticates again right away.
+    pub fn reconnect() {
+        SPOTIFY_RECONNECT.notify_one();
+    }
+
+    async fn manage() {
+        let mut attempt = 0;
+
+        loop {
+            let wait = if !Self::has_credentials() {
+                *SPOTIFY_STATUS.lock().await = SpotifyStatus::Disabled;
+                None
+            } else {
+                match Self::auth().await {
+                    Ok(spotify) => {
+                        attempt = 0;
+
+                        let lifetime = Self::token_lifetime(&spotify).await;
+                        let refreshed_at = SystemTime::now();
+
+                        *SPOTIFY.lock().await = Ok(spotify);
+                        *SPOTIFY_STATUS.lock().await = SpotifyStatus::Connected {
+                            refreshed_at,
+                            expires_at: refreshed_at + lifetime,
+                        };
+
+                        // refresh a bit before the token actually expires
+                        Some(lifetime.saturating_sub(TOKEN_REFRESH_MARGIN))
+                    }
+                    Err(err) => {
+                        attempt += 1;
+                        let delay = min(
+                            AUTH_RETRY_DELAY.saturating_mul(2u32.saturating_pow(attempt - 1)),
+                            AUTH_RETRY_MAX_DELAY,
+                        );
+
+                        println!(
+                            "[ERROR] Spotify authentication attempt {} failed due to {}",
+                            attempt, err
+                        );
+
+                        *SPOTIFY_STATUS.lock().await = SpotifyStatus::Retrying {
+                            attempt,
+                            error: err.to_string(),
+                            retry_at: SystemTime::now() + delay,
+                        };
+
+                        // a previously issued token stays usable until it expires, so keep it around
+                        let mut spotify = SPOTIFY.lock().await;
+                        if spotify.is_err() {
+                            *spotify = Err(err);
+                        }
+                        drop(spotify);
+
+                        Some(delay)
+                    }
+                }
+            };
+
+            match wait {
+                Some(wait) => {
+                    tokio::select! {
+                        _ = sleep(wait) => {}
+                        _ = SPOTIFY_RECONNECT.notified() => attempt = 0,
+                    }
+                }
+                None => SPOTIFY_RECONNECT.notified().await,
+            }
+        }
+    }
+
+    fn has_credentials() -> bool {
+        env::var("SPOTIFY_CLIENT_ID").is_ok() && env::var("SPOTIFY_CLIENT_SECRET").is_ok()
+    }
+
+    async fn token_lifetime(spotify: &ClientCredsSpotify) -> Duration {
+        let Ok(token) = spotify.token.lock().await else {
+            return TOKEN_DEFAULT_LIFETIME;
+        };
+
+        token
+            .as_ref()
+            .and_then(|token| token.expires_in.to_std().ok())
+            .unwrap_or(TOKEN_DEFAULT_LIFETIME)
+    }
+
     pub async fn auth() -> Result<ClientCredsSpotify, ParrotError> {
         let spotify_client_id = env::var("SPOTIFY_CLIENT_ID")
             .map_err(|_| ParrotError::Other("missing spotify client ID"))?;
@@ -62,6 +195,16 @@ impl Spotify {
         spotify: &ClientCredsSpotify,
         query: &str,
     ) -> Result<QueryType, ParrotError> {
+        let (media_type, media_id) = Self::parse_link(query)?;
+
+        match media_type {
+            MediaType::Track => Self::get_track_info(spotify, &media_id).await,
+            MediaType::Album => Self::get_album_info(spotify, &media_id).await,
+            MediaType::Playlist => Self::get_playlist_info(spotify, &media_id).await,
+        }
+    }
+
+    pub fn parse_link(query: &str) -> Result<(MediaType, String), ParrotError> {
         let captures = SPOTIFY_QUERY_REGEX
             .captures(query)
             .ok_or(ParrotError::Other(SPOTIFY_INVALID_QUERY))?;
@@ -79,11 +222,7 @@ impl Spotify {
             .ok_or(ParrotError::Other(SPOTIFY_INVALID_QUERY))?
             .as_str();
 
-        match media_type {
-            MediaType::Track => Self::get_track_info(spotify, media_id).await,
-            MediaType::Album => Self::get_album_info(spotify, media_id).await,
-            MediaType::Playlist => Self::get_playlist_info(spotify, media_id).await,
-        }
+        Ok((media_type, media_id.to_string()))
     }
 
     async fn get_track_info(
@@ -156,7 +295,7 @@ impl Spotify {
         Ok(QueryType::KeywordList(query_list))
     }
 
-    fn build_query(artists: &str, track_name: &str) -> String {
+    pub fn build_query(artists: &str, track_name: &str) -> String {
         format!("{} - {}", artists, track_name)
     }
 
@@ -165,3 +304,27 @@ impl Spotify {
         artist_names.join(" ")
     }
 }
+
+#[async_trait]
+impl Source for Spotify {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "Spotify",
+            streams_from_origin: false,
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        url.host_str() == Some("open.spotify.com")
+    }
+
+    async fn resolve(
+        &self,
+        _http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        let spotify = SPOTIFY.lock().await;
+        let spotify = verify(spotify.as_ref(), ParrotError::Other(SPOTIFY_AUTH_FAILED))?;
+        Ok(vec![Self::extract(spotify, url.as_str()).await?])
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    messaging::messages::SOURCE_INVALID_QUERY,
+    sources::{
+        Source, SourceMetadata, build_keywords,
+        page::{OEmbed, extract_meta_contents, fetch_oembed, fetch_text},
+    },
+};
+use serenity::async_trait;
+use url::Url;
+
+const TIDAL_BROWSE_URL: &str = "https://tidal.com/browse";
+const TIDAL_OEMBED_URL: &str = "https://oembed.tidal.com/";
+const MAX_COLLECTION_SIZE: usize = 100;
+
+#[derive(Debug, PartialEq)]
+pub enum TidalLink {
+    Track(String),
+    Album(String),
+    Playlist(String),
+}
+
+pub struct Tidal;
+
+impl Tidal {
+    pub fn parse_link(url: &Url) -> Option<TidalLink> {
+        let segments: Vec<&str> = url
+            .path_segments()?
+            .filter(|s| !s.is_empty() && *s != "browse")
+            .collect();
+
+        let id = segments.get(1)?.to_string();
+
+        match *segments.first()? {
+            "track" => Some(TidalLink::Track(id)),
+            "album" => Some(TidalLink::Album(id)),
+            "playlist" => Some(TidalLink::Playlist(id)),
+            _ => None,
+        }
+    }
+
+    pub fn oembed_to_keywords(oembed: &OEmbed) -> Option<String> {
+        let title = oembed.title.as_deref()?;
+        Some(build_keywords(oembed.author_name.as_deref(), title))
+    }
+
+    /// Tidal's public pages list the tracks of an album or playlist in `music:song` meta tags.
+    pub fn parse_collection_page(html: &str) -> Vec<String> {
+        extract_meta_contents(html, "music:song")
+            .into_iter()
+            .take(MAX_COLLECTION_SIZE)
+            .collect()
+    }
+
+    async fn resolve_track(
+        http_client: &reqwest::Client,
+        url: &str,
+    ) -> Result<Option<String>, ParrotError> {
+        let oembed = fetch_oembed(http_client, TIDAL_OEMBED_URL, url).await?;
+        Ok(Self::oembed_to_keywords(&oembed))
+    }
+}
+
+#[async_trait]
+impl Source for Tidal {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "Tidal",
+            streams_from_origin: false,
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        matches!(
+            url.host_str(),
+            Some("tidal.com" | "www.tidal.com" | "listen.tidal.com")
+        )
+    }
+
+    async fn resolve(
+        &self,
+        http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        let link = Self::parse_link(url).ok_or(ParrotError::Other(SOURCE_INVALID_QUERY))?;
+
+        let page_url = match &link {
+            TidalLink::Track(id) => {
+                let track_url = format!("{}/track/{}", TIDAL_BROWSE_URL, id);
+                let keywords = Self::resolve_track(http_client, &track_url).await?;
+                return Ok(keywords.into_iter().map(QueryType::Keywords).collect());
+            }
+            TidalLink::Album(id) => format!("{}/album/{}", TIDAL_BROWSE_URL, id),
+            TidalLink::Playlist(id) => format!("{}/playlist/{}", TIDAL_BROWSE_URL, id),
+        };
+
+        let html = fetch_text(http_client, &page_url).await?;
+        let mut queries = Vec::new();
+
+        // a missing track shouldn't take the rest of the album down with it
+        for track_url in Self::parse_collection_page(&html) {
+            if let Ok(Some(keywords)) = Self::resolve_track(http_client, &track_url).await {
+                queries.push(QueryType::Keywords(keywords));
+            }
+        }
+
+        Ok(queries)
+    }
+}
@@ -1,196 +1,42 @@
 use crate::{
-    commands::play::{Mode, QueryType},
-    sources::ffmpeg::ffmpeg,
+    commands::play::QueryType,
+    errors::ParrotError,
+    sources::{Source, SourceMetadata},
 };
-use serde_json::Value;
 use serenity::async_trait;
-use songbird::input::{codecs::RawReader, Input, Metadata, YoutubeDl};
-use std::{
-    io::{BufRead, BufReader, Read},
-    process::Command,
-    process::{Child, Stdio},
-    time::Duration,
-};
-use tokio::{process::Command as TokioCommand, task};
-
-const NEWLINE_BYTE: u8 = 0xA;
+use url::Url;
 
-pub struct YouTube {}
+/// YouTube and every other site yt-dlp knows how to stream from.
+pub struct YouTube;
 
 impl YouTube {
-    pub fn extract(query: &str) -> Option<QueryType> {
-        if query.contains("list=") {
-            Some(QueryType::PlaylistLink(query.to_string()))
+    pub fn classify(url: &Url) -> QueryType {
+        if url.query_pairs().any(|(key, _)| key == "list") {
+            QueryType::PlaylistLink(url.to_string())
         } else {
-            Some(QueryType::VideoLink(query.to_string()))
+            QueryType::VideoLink(url.to_string())
         }
     }
 }
 
-pub struct YouTubeRestartable {}
-
-impl YouTubeRestartable {
-    pub async fn ytdl<P: AsRef<str> + Send + Clone + Sync + 'static>(uri: P) -> Input {
-        YoutubeDl::new(client, url)
-    }
-
-    pub async fn ytdl_search<P: AsRef<str> + Send + Clone + Sync + 'static>(
-        uri: P,
-    ) -> Result<Input> {
-        let uri = format!("ytsearch:{}", uri.as_ref());
-        Input::new(YouTubeRestarter { uri }, lazy).await
-    }
-
-    pub async fn ytdl_playlist(uri: &str, mode: Mode) -> Option<Vec<String>> {
-        let mut args = vec![uri, "--flat-playlist", "-j"];
-        match mode {
-            Mode::Reverse => args.push("--playlist-reverse"),
-            Mode::Shuffle => args.push("--playlist-random"),
-            _ => {}
+#[async_trait]
+impl Source for YouTube {
+    fn metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: "yt-dlp",
+            streams_from_origin: true,
         }
-
-        let mut child = Command::new("yt-dlp")
-            .args(args)
-            .stdout(Stdio::piped())
-            .spawn()
-            .unwrap();
-
-        let Some(stdout) = &mut child.stdout else {
-            return None;
-        };
-
-        let reader = BufReader::new(stdout);
-
-        let lines = reader.lines().flatten().map(|line| {
-            let entry: Value = serde_json::from_str(&line).unwrap();
-            entry
-                .get("webpage_url")
-                .unwrap()
-                .as_str()
-                .unwrap()
-                .to_string()
-        });
-
-        Some(lines.collect())
     }
-}
-
-struct YouTubeRestarter<P>
-where
-    P: AsRef<str> + Send + Sync,
-{
-    uri: P,
-}
-
-#[async_trait]
-impl<P> Restart for YouTubeRestarter<P>
-where
-    P: AsRef<str> + Send + Clone + Sync,
-{
-    async fn call_restart(&mut self, time: Option<Duration>) -> SongbirdResult<Input> {
-        let (yt, metadata) = ytdl(self.uri.as_ref()).await?;
-
-        let Some(time) = time else {
-            return ffmpeg(yt, metadata, &[]).await;
-        };
 
-        let ts = format!("{:.3}", time.as_secs_f64());
-        ffmpeg(yt, metadata, &["-ss", &ts]).await
+    fn matches(&self, url: &Url) -> bool {
+        matches!(url.scheme(), "http" | "https") && url.host_str().is_some()
     }
 
-    async fn lazy_init(&mut self) -> SongbirdResult<(Option<Metadata>, Codec, RawReader)> {
-        _ytdl_metadata(self.uri.as_ref())
-            .await
-            .map(|m| (Some(m), Codec::FloatPcm, Container::Raw))
+    async fn resolve(
+        &self,
+        _http_client: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Vec<QueryType>, ParrotError> {
+        Ok(vec![Self::classify(url)])
     }
 }
-
-async fn ytdl(uri: &str) -> Result<(Child, Metadata), SongbirdError> {
-    let ytdl_args = [
-        "-j",            // print JSON information for video for metadata
-        "-q",            // don't print progress logs (this messes with -o -)
-        "--no-simulate", // ensure video is downloaded regardless of printing
-        "-f",
-        "webm[abr>0]/bestaudio/best", // select best quality audio-only
-        "-R",
-        "infinite",        // infinite number of download retries
-        "--no-playlist",   // only download the video if URL also has playlist info
-        "--ignore-config", // disable all configuration files for a yt-dlp run
-        "--no-warnings",   // don't print out warnings
-        uri,
-        "-o",
-        "-", // stream data to stdout
-    ];
-
-    let mut yt = Command::new("yt-dlp")
-        .args(ytdl_args)
-        .stdin(Stdio::null())
-        .stderr(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()?;
-
-    // track info json (for metadata) is piped to stderr by design choice of yt-dlp
-    // the actual track is streamed to stdout
-    let stderr = yt.stderr.take();
-    let (returned_stderr, value) = task::spawn_blocking(move || {
-        let mut s = stderr.unwrap();
-        let out: SongbirdResult<Value> = {
-            let mut o_vec = vec![];
-            let mut serde_read = BufReader::new(s.by_ref());
-
-            if let Ok(len) = serde_read.read_until(NEWLINE_BYTE, &mut o_vec) {
-                serde_json::from_slice(&o_vec[..len]).map_err(|err| SongbirdError::Json {
-                    error: err,
-                    parsed_text: std::str::from_utf8(&o_vec).unwrap_or_default().to_string(),
-                })
-            } else {
-                Result::Err(SongbirdError::Metadata)
-            }
-        };
-
-        (s, out)
-    })
-    .await
-    .map_err(|_| SongbirdError::Metadata)?;
-
-    let metadata = Metadata::from_ytdl_output(value?);
-    yt.stderr = Some(returned_stderr);
-
-    Ok((yt, metadata))
-}
-
-async fn _ytdl_metadata(uri: &str) -> SongbirdResult<Metadata> {
-    let ytdl_args = [
-        "-j", // print JSON information for video for metadata
-        "-R",
-        "infinite",        // infinite number of download retries
-        "--no-playlist",   // only download the video if URL also has playlist info
-        "--ignore-config", // disable all configuration files for a yt-dlp run
-        "--no-warnings",   // don't print out warnings
-        uri,
-        "-o",
-        "-", // stream data to stdout
-    ];
-
-    let youtube_dl_output = TokioCommand::new("yt-dlp")
-        .args(ytdl_args)
-        .stdin(Stdio::null())
-        .output()
-        .await?;
-
-    let o_vec = youtube_dl_output.stderr;
-
-    // read until newline byte
-    let end = (o_vec)
-        .iter()
-        .position(|el| *el == NEWLINE_BYTE)
-        .unwrap_or(o_vec.len());
-
-    let value = serde_json::from_slice(&o_vec[..end]).map_err(|err| SongbirdError::Json {
-        error: err,
-        parsed_text: std::str::from_utf8(&o_vec).unwrap_or_default().to_string(),
-    })?;
-
-    let metadata = Metadata::from_ytdl_output(value);
-    Ok(metadata)
-}
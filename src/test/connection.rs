@@ -0,0 +1,52 @@
+use serenity::{all::ChannelId, prelude::Mentionable};
+
+use crate::{
+    connection::{Connection, check_command_connection, get_stage_topic},
+    errors::ParrotError,
+};
+
+#[test]
+fn test_get_stage_topic() {
+    let result = get_stage_topic("Never Gonna Give You Up");
+    assert_eq!(result, "Never Gonna Give You Up");
+
+    let title = "a".repeat(120);
+    assert_eq!(get_stage_topic(&title), title);
+
+    let result = get_stage_topic(&"a".repeat(200));
+    assert_eq!(result.chars().count(), 120);
+    assert!(result.ends_with('…'));
+
+    // multi-byte characters count once, like discord counts them
+    let result = get_stage_topic(&"🦜".repeat(150));
+    assert_eq!(result.chars().count(), 120);
+}
+
+#[test]
+fn test_check_command_connection() {
+    let (bot_channel, user_channel) = (ChannelId::new(1), ChannelId::new(2));
+
+    let result = check_command_connection("skip", Connection::Mutual(bot_channel, bot_channel));
+    assert_eq!(result, Ok(()));
+
+    let result = check_command_connection("skip", Connection::Separate(bot_channel, user_channel));
+    assert_eq!(result, Err(ParrotError::WrongVoiceChannel));
+
+    let result = check_command_connection("skip", Connection::Bot(bot_channel));
+    assert_eq!(
+        result,
+        Err(ParrotError::AuthorDisconnected(bot_channel.mention()))
+    );
+
+    let result = check_command_connection("np", Connection::Neither);
+    assert_eq!(result, Err(ParrotError::NotConnected));
+
+    let result = check_command_connection("summon", Connection::User(user_channel));
+    assert_eq!(result, Ok(()));
+
+    let result = check_command_connection("summon", Connection::Bot(bot_channel));
+    assert_eq!(result, Err(ParrotError::AuthorNotFound));
+
+    let result = check_command_connection("version", Connection::Neither);
+    assert_eq!(result, Ok(()));
+}
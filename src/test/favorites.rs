@@ -0,0 +1,55 @@
+use serenity::model::id::UserId;
+
+use crate::{
+    errors::ParrotError,
+    guild::favorites::{Favorite, MAX_FAVORITES, UserFavorites},
+    messaging::messages::{FAIL_ALREADY_LIKED, FAIL_FAVORITES_FULL},
+};
+
+fn favorite(n: usize) -> Favorite {
+    Favorite {
+        title: format!("Track {}", n),
+        source_url: format!("https://www.youtube.com/watch?v={}", n),
+    }
+}
+
+#[test]
+fn test_like_favorites() {
+    let mut user_favorites = UserFavorites::new(UserId::new(1));
+
+    assert!(user_favorites.like(favorite(1)).is_ok());
+    assert!(user_favorites.like(favorite(2)).is_ok());
+    assert_eq!(user_favorites.tracks, vec![favorite(1), favorite(2)]);
+
+    let renamed = Favorite {
+        title: "Same track, new title".to_string(),
+        ..favorite(1)
+    };
+    assert_eq!(
+        user_favorites.like(renamed),
+        Err(ParrotError::Other(FAIL_ALREADY_LIKED))
+    );
+    assert_eq!(user_favorites.tracks.len(), 2);
+}
+
+#[test]
+fn test_like_full_favorites() {
+    let mut user_favorites = UserFavorites::new(UserId::new(1));
+    user_favorites.tracks = (0..MAX_FAVORITES).map(favorite).collect();
+
+    assert_eq!(
+        user_favorites.like(favorite(MAX_FAVORITES)),
+        Err(ParrotError::Other(FAIL_FAVORITES_FULL))
+    );
+    assert_eq!(user_favorites.tracks.len(), MAX_FAVORITES);
+}
+
+#[test]
+fn test_remove_favorites() {
+    let mut user_favorites = UserFavorites::new(UserId::new(1));
+    user_favorites.tracks = (0..3).map(favorite).collect();
+
+    assert_eq!(user_favorites.remove(1), Some(favorite(1)));
+    assert_eq!(user_favorites.remove(2), None);
+    assert_eq!(user_favorites.tracks, vec![favorite(0), favorite(2)]);
+}
@@ -1,2 +1,16 @@
+pub mod connection;
 pub mod errors;
+pub mod favorites;
+pub mod now_playing;
+pub mod permissions;
+pub mod play;
+pub mod play_log;
+pub mod queue;
+pub mod queue_cleanup;
+pub mod quiz;
+pub mod schedule;
+pub mod sleep;
+pub mod sources;
+pub mod undo;
 pub mod utils;
+pub mod voteskip;
@@ -0,0 +1,27 @@
+use std::str::FromStr;
+
+use crate::{commands::now_playing::get_button_command, guild::settings::LoopMode};
+
+#[test]
+fn test_get_button_command() {
+    assert_eq!(get_button_command("np_pause", false), Some("pause"));
+    assert_eq!(get_button_command("np_pause", true), Some("resume"));
+    assert_eq!(get_button_command("np_skip", false), Some("skip"));
+    assert_eq!(get_button_command("np_loop", false), Some("loop"));
+    assert_eq!(get_button_command("np_shuffle", true), Some("shuffle"));
+    assert_eq!(get_button_command("np_stop", false), Some("stop"));
+    assert_eq!(get_button_command("np_like", true), Some("like"));
+    assert_eq!(get_button_command("voteskip", false), None);
+}
+
+#[test]
+fn test_loop_mode_cycle() {
+    assert_eq!(LoopMode::Off.next(), LoopMode::Track);
+    assert_eq!(LoopMode::Track.next(), LoopMode::Queue);
+    assert_eq!(LoopMode::Queue.next(), LoopMode::Off);
+
+    assert_eq!(LoopMode::from_str("track"), Ok(LoopMode::Track));
+    assert_eq!(LoopMode::from_str("queue"), Ok(LoopMode::Queue));
+    assert_eq!(LoopMode::from_str("off"), Ok(LoopMode::Off));
+    assert!(LoopMode::from_str("forever").is_err());
+}
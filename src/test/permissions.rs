@@ -0,0 +1,51 @@
+use serenity::model::id::{ChannelId, GuildId};
+use std::str::FromStr;
+
+use crate::{
+    guild::settings::{GuildSettings, PermissionLevel},
+    permissions::default_permission_level,
+};
+
+#[test]
+fn test_default_permission_level() {
+    assert_eq!(
+        default_permission_level("play"),
+        Some(PermissionLevel::Everyone)
+    );
+    assert_eq!(default_permission_level("stop"), Some(PermissionLevel::Dj));
+    assert_eq!(
+        default_permission_level("managesources"),
+        Some(PermissionLevel::Admin)
+    );
+    assert_eq!(default_permission_level("dance"), None);
+}
+
+#[test]
+fn test_get_permission_level() {
+    let mut settings = GuildSettings::new(GuildId::new(1));
+    assert_eq!(settings.get_permission_level("clear"), PermissionLevel::Dj);
+
+    settings
+        .command_permissions
+        .insert("clear".to_string(), PermissionLevel::Everyone);
+    assert_eq!(
+        settings.get_permission_level("clear"),
+        PermissionLevel::Everyone
+    );
+
+    assert_eq!(
+        PermissionLevel::from_str("admin"),
+        Ok(PermissionLevel::Admin)
+    );
+    assert!(PermissionLevel::from_str("default").is_err());
+}
+
+#[test]
+fn test_is_channel_bound() {
+    let mut settings = GuildSettings::new(GuildId::new(1));
+    assert!(settings.is_channel_bound(ChannelId::new(2)));
+
+    settings.bound_channels.insert(ChannelId::new(3));
+    assert!(!settings.is_channel_bound(ChannelId::new(2)));
+    assert!(settings.is_channel_bound(ChannelId::new(3)));
+}
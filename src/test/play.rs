@@ -0,0 +1,95 @@
+use serenity::model::id::GuildId;
+use songbird::input::AuxMetadata;
+
+use crate::{
+    commands::play::{QueryType, get_fallback_queries, get_requery_shapes, is_already_queued},
+    guild::settings::{GuildSettings, TrackFallback},
+};
+
+#[test]
+fn test_get_requery_shapes() {
+    let shapes = get_requery_shapes("Daft Punk - Get Lucky (feat. Pharrell Williams) [Radio Edit]");
+    assert_eq!(shapes, vec!["Daft Punk - Get Lucky", "Get Lucky"]);
+
+    let shapes = get_requery_shapes("Calvin Harris ft. Rihanna - This Is What You Came For");
+    assert_eq!(
+        shapes,
+        vec![
+            "Calvin Harris - This Is What You Came For",
+            "This Is What You Came For"
+        ]
+    );
+
+    let shapes = get_requery_shapes("Get Lucky");
+    assert!(shapes.is_empty());
+}
+
+#[test]
+fn test_get_fallback_queries() {
+    let query_type = QueryType::Keywords("Daft Punk - Get Lucky".to_string());
+
+    let queries = get_fallback_queries(&query_type, TrackFallback::Both);
+    assert!(matches!(
+        queries.as_slice(),
+        [QueryType::Keywords(title), QueryType::VideoLink(search)]
+            if title == "Get Lucky" && search == "scsearch1:Daft Punk - Get Lucky"
+    ));
+
+    let queries = get_fallback_queries(&query_type, TrackFallback::Requery);
+    assert!(matches!(queries.as_slice(), [QueryType::Keywords(_)]));
+
+    assert!(get_fallback_queries(&query_type, TrackFallback::Off).is_empty());
+
+    let query_type =
+        QueryType::VideoLink("https://www.youtube.com/watch?v=5NV6Rdv1a3I".to_string());
+    assert!(get_fallback_queries(&query_type, TrackFallback::Both).is_empty());
+}
+
+#[test]
+fn test_get_track_fallback() {
+    let mut settings = GuildSettings::new(GuildId::new(1));
+
+    // soundcloud isn't allowed by default, so it isn't searched either
+    settings.track_fallback = TrackFallback::Both;
+    assert_eq!(settings.get_track_fallback(), TrackFallback::Requery);
+    settings.track_fallback = TrackFallback::AlternateSource;
+    assert_eq!(settings.get_track_fallback(), TrackFallback::Off);
+
+    settings
+        .allowed_domains
+        .insert("soundcloud.com".to_string());
+    assert_eq!(
+        settings.get_track_fallback(),
+        TrackFallback::AlternateSource
+    );
+    settings.track_fallback = TrackFallback::Both;
+    assert_eq!(settings.get_track_fallback(), TrackFallback::Both);
+
+    settings.allowed_domains.clear();
+    settings.banned_domains.insert("soundcloud.com".to_string());
+    assert_eq!(settings.get_track_fallback(), TrackFallback::Requery);
+}
+
+#[test]
+fn test_is_already_queued() {
+    let queued = [
+        AuxMetadata {
+            source_url: Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string()),
+            ..Default::default()
+        },
+        AuxMetadata::default(),
+    ];
+
+    assert!(is_already_queued(
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+        queued.iter()
+    ));
+    assert!(!is_already_queued(
+        "https://www.youtube.com/watch?v=9bZkp7q19f0",
+        queued.iter()
+    ));
+    assert!(!is_already_queued(
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+        [].iter()
+    ));
+}
@@ -0,0 +1,149 @@
+use serenity::model::id::{GuildId, UserId};
+use std::{env, fs};
+
+use crate::guild::{
+    play_log::{
+        PLAY_LOG_RETENTION, PlayLogEntry, PlayStats, TrackPlays, load_play_log, log_play,
+        prune_play_log, trim_play_log_lines,
+    },
+    schedule::SECONDS_PER_DAY,
+};
+
+// the fifth day after the epoch was a monday
+const MONDAY: u64 = SECONDS_PER_DAY * 4;
+const TUESDAY: u64 = SECONDS_PER_DAY * 5;
+
+fn entry(
+    title: &str,
+    source_url: Option<&str>,
+    requester: Option<u64>,
+    started_at: u64,
+) -> PlayLogEntry {
+    PlayLogEntry {
+        title: title.to_string(),
+        source_url: source_url.map(|url| url.to_string()),
+        requester: requester.map(UserId::new),
+        ended_at: started_at + 200,
+        play_time: 200,
+    }
+}
+
+#[test]
+fn test_play_stats() {
+    let url = "https://www.youtube.com/watch?v=a";
+    let entries = vec![
+        entry("A", Some(url), Some(1), MONDAY + 21 * 3600),
+        entry("A (renamed)", Some(url), Some(2), MONDAY + 21 * 3600 + 1800),
+        entry("B", None, Some(1), TUESDAY + 9 * 3600),
+        entry("A", Some(url), None, TUESDAY + 21 * 3600 + 600),
+    ];
+
+    let stats = PlayStats::new(&entries);
+
+    assert_eq!(stats.plays, 4);
+    assert_eq!(stats.play_time, 800);
+    assert_eq!(
+        stats.top_tracks,
+        vec![
+            TrackPlays {
+                title: "A".to_string(),
+                source_url: Some(url.to_string()),
+                plays: 3,
+            },
+            TrackPlays {
+                title: "B".to_string(),
+                source_url: None,
+                plays: 1,
+            },
+        ]
+    );
+    assert_eq!(
+        stats.top_requesters,
+        vec![(UserId::new(1), 2), (UserId::new(2), 1)]
+    );
+    assert_eq!(stats.busiest_hour, Some(21));
+    // mondays and tuesdays are tied, so the earlier day wins
+    assert_eq!(stats.busiest_weekday, Some(0));
+}
+
+#[test]
+fn test_empty_play_stats() {
+    let stats = PlayStats::new(&[]);
+
+    assert_eq!(stats.plays, 0);
+    assert_eq!(stats.play_time, 0);
+    assert!(stats.top_tracks.is_empty());
+    assert!(stats.top_requesters.is_empty());
+    assert_eq!(stats.busiest_hour, None);
+    assert_eq!(stats.busiest_weekday, None);
+}
+
+#[test]
+fn test_play_log_entry_started_at() {
+    let played = entry("A", None, None, 1000);
+    assert_eq!(played.started_at(), 1000);
+
+    let clock_skewed = PlayLogEntry {
+        ended_at: 100,
+        play_time: 200,
+        ..played
+    };
+    assert_eq!(clock_skewed.started_at(), 0);
+}
+
+#[test]
+fn test_trim_play_log_lines() {
+    let lines: Vec<String> = ["aaaa", "bb", "cccccc"]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+    let trimmed = |max_entries, max_size| {
+        let mut lines = lines.clone();
+        trim_play_log_lines(&mut lines, max_entries, max_size);
+        lines
+    };
+
+    assert_eq!(trimmed(10, 15), lines);
+    assert_eq!(trimmed(2, 100), lines[1..]);
+    assert_eq!(trimmed(10, 10), lines[1..]);
+    assert_eq!(trimmed(10, 9), lines[2..]);
+    assert!(trimmed(10, 6).is_empty());
+    assert!(trimmed(0, 100).is_empty());
+}
+
+#[test]
+fn test_play_log_retention() {
+    let path = env::temp_dir().join(format!("parrot-play-log-{}", std::process::id()));
+    fs::remove_dir_all(&path).ok();
+
+    // the path is only read once, and no other test touches the play log on disk
+    unsafe { env::set_var("PLAY_LOG_PATH", &path) };
+
+    let guild_id = GuildId::new(1);
+    let now = PLAY_LOG_RETENTION * 2;
+
+    let mut expired = entry("A", None, Some(1), 0);
+    expired.ended_at = now - PLAY_LOG_RETENTION;
+    let mut recent = entry("B", None, Some(2), 0);
+    recent.ended_at = now - 60;
+
+    assert!(load_play_log(guild_id, now).unwrap().is_empty());
+
+    log_play(guild_id, &expired).unwrap();
+    log_play(guild_id, &recent).unwrap();
+
+    let log_path = path.join(format!("{}.jsonl", guild_id));
+    let mut contents = fs::read_to_string(&log_path).unwrap();
+    contents.push_str("not a play\n");
+    fs::write(&log_path, contents).unwrap();
+
+    assert_eq!(load_play_log(guild_id, now).unwrap(), vec![recent.clone()]);
+
+    prune_play_log(guild_id, now).unwrap();
+    let contents = fs::read_to_string(&log_path).unwrap();
+    assert_eq!(contents.lines().count(), 1);
+    assert_eq!(load_play_log(guild_id, now).unwrap(), vec![recent]);
+
+    fs::remove_dir_all(&path).ok();
+}
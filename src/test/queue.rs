@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::{
+    commands::queue::{
+        QueueStatus, QueueSummary, fuzzy_score, get_queue_action_command, summarize_queue,
+    },
+    guild::settings::LoopMode,
+};
+
+#[test]
+fn test_get_queue_action_command() {
+    assert_eq!(get_queue_action_command("next"), Some("superplay"));
+    assert_eq!(get_queue_action_command("top"), Some("skip"));
+    assert_eq!(get_queue_action_command("remove"), Some("remove"));
+    assert_eq!(get_queue_action_command("details"), None);
+}
+
+#[test]
+fn test_summarize_queue() {
+    let durations = [
+        Some(Duration::from_secs(200)),
+        Some(Duration::from_secs(180)),
+        Some(Duration::from_secs(240)),
+    ];
+
+    let result = summarize_queue(&durations, Duration::from_secs(50));
+    let expected = QueueSummary {
+        tracks: 3,
+        remaining: Duration::from_secs(570),
+        has_unknown: false,
+    };
+    assert_eq!(result, expected);
+
+    // livestreams don't have a duration to add up
+    let durations = [None, Some(Duration::from_secs(180))];
+    let result = summarize_queue(&durations, Duration::from_secs(3000));
+    let expected = QueueSummary {
+        tracks: 2,
+        remaining: Duration::from_secs(180),
+        has_unknown: true,
+    };
+    assert_eq!(result, expected);
+
+    // a position past the end doesn't eat into the rest of the queue
+    let durations = [
+        Some(Duration::from_secs(100)),
+        Some(Duration::from_secs(60)),
+    ];
+    let result = summarize_queue(&durations, Duration::from_secs(120));
+    assert_eq!(result.remaining, Duration::from_secs(60));
+
+    let result = summarize_queue(&[], Duration::ZERO);
+    assert_eq!(result.tracks, 0);
+    assert_eq!(result.remaining, Duration::ZERO);
+}
+
+#[test]
+fn test_queue_summary_display() {
+    let summary = QueueSummary {
+        tracks: 1,
+        remaining: Duration::from_secs(95),
+        has_unknown: false,
+    };
+    assert_eq!(summary.to_string(), "🎶 **1 track** • ⏳ `01:35` remaining");
+
+    let summary = QueueSummary {
+        tracks: 4,
+        remaining: Duration::from_secs(95),
+        has_unknown: true,
+    };
+    assert_eq!(
+        summary.to_string(),
+        "🎶 **4 tracks** • ⏳ `01:35+` remaining"
+    );
+}
+
+#[test]
+fn test_queue_status_display() {
+    let status = QueueStatus {
+        loop_mode: LoopMode::Off,
+        autopause: false,
+        volume: 1.0,
+    };
+    assert_eq!(status.to_string(), "🔊 100%");
+
+    let status = QueueStatus {
+        loop_mode: LoopMode::Queue,
+        autopause: true,
+        volume: 0.5,
+    };
+    assert_eq!(status.to_string(), "🔁 Queue loop • ⏯️ Autopause • 🔊 50%");
+
+    let status = QueueStatus {
+        loop_mode: LoopMode::Track,
+        autopause: false,
+        volume: 0.25,
+    };
+    assert_eq!(status.to_string(), "🔂 Track loop • 🔊 25%");
+}
+
+#[test]
+fn test_fuzzy_score() {
+    let title = "Rick Astley - Never Gonna Give You Up (Official Music Video)";
+
+    assert_eq!(fuzzy_score("never gonna", title), Some(0));
+    assert_eq!(fuzzy_score("NEVER, gonna!", title), Some(0));
+    assert_eq!(fuzzy_score("gonna never", title), Some(1));
+    assert_eq!(fuzzy_score("nvr gna", title), Some(7));
+
+    assert_eq!(fuzzy_score("metallica", title), None);
+    assert_eq!(fuzzy_score("rvu", title), None);
+    assert_eq!(fuzzy_score("", title), None);
+    assert_eq!(fuzzy_score("!?", title), None);
+}
@@ -0,0 +1,45 @@
+use crate::{
+    commands::{play::QueryType, queue_cleanup::find_duplicates},
+    guild::stored_queue::GuildStoredQueue,
+};
+
+#[test]
+fn test_find_duplicates() {
+    let source_urls = [
+        Some("https://www.youtube.com/watch?v=a"),
+        Some("https://www.youtube.com/watch?v=b"),
+        Some("https://www.youtube.com/watch?v=a"),
+        None,
+        None,
+        Some("https://www.youtube.com/watch?v=b"),
+        Some("https://www.youtube.com/watch?v=a"),
+    ];
+    assert_eq!(find_duplicates(&source_urls), vec![2, 5, 6]);
+
+    assert!(find_duplicates(&[]).is_empty());
+    assert!(find_duplicates(&[Some("https://www.youtube.com/watch?v=a"), None]).is_empty());
+}
+
+#[test]
+fn test_stored_queue_sync() {
+    let mut stored_queue = GuildStoredQueue::new();
+    stored_queue.queue = vec![
+        QueryType::PlaylistLink("https://www.youtube.com/playlist?list=a".to_string()),
+        QueryType::Keywords("never gonna give you up".to_string()),
+    ];
+
+    stored_queue.sync(&[
+        Some("https://www.youtube.com/watch?v=a"),
+        None,
+        Some("https://example.com/track.mp3"),
+    ]);
+
+    assert_eq!(stored_queue.queue.len(), 2);
+    assert!(matches!(
+        &stored_queue.queue[0],
+        QueryType::VideoLink(url) if url == "https://www.youtube.com/watch?v=a"
+    ));
+
+    stored_queue.sync(&[]);
+    assert!(stored_queue.queue.is_empty());
+}
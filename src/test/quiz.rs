@@ -0,0 +1,158 @@
+use serenity::model::id::UserId;
+use std::{collections::HashMap, time::Duration};
+
+use crate::commands::quiz::{
+    get_clip_start, get_quiz_answers, get_similarity, is_blocked_during_quiz, is_correct_answer,
+    normalize_answer, pick_choices, rank_scores,
+};
+
+#[test]
+fn test_normalize_answer() {
+    assert_eq!(
+        normalize_answer("  Don't Stop Me Now!! "),
+        "don t stop me now".to_string()
+    );
+    assert_eq!(
+        normalize_answer("Beyoncé — HALO"),
+        "beyoncé halo".to_string()
+    );
+    assert_eq!(normalize_answer("?!"), "".to_string());
+}
+
+#[test]
+fn test_get_quiz_answers() {
+    let answers = get_quiz_answers(
+        "Rick Astley - Never Gonna Give You Up (Official Video)",
+        None,
+    );
+
+    assert_eq!(
+        answers,
+        vec![
+            "rick astley never gonna give you up official video".to_string(),
+            "rick astley never gonna give you up".to_string(),
+            "never gonna give you up".to_string(),
+            "rick astley".to_string(),
+        ]
+    );
+
+    let answers = get_quiz_answers("Bohemian Rhapsody", Some("Queen"));
+    assert_eq!(
+        answers,
+        vec!["bohemian rhapsody".to_string(), "queen".to_string()]
+    );
+}
+
+#[test]
+fn test_is_correct_answer() {
+    let answers = get_quiz_answers(
+        "Rick Astley - Never Gonna Give You Up (Official Video)",
+        None,
+    );
+
+    assert!(is_correct_answer("never gonna give you up", &answers));
+    assert!(is_correct_answer("Never gona give you up!", &answers));
+    assert!(is_correct_answer("rick astly", &answers));
+    assert!(is_correct_answer(
+        "Rick Astley - Never Gonna Give You Up",
+        &answers
+    ));
+
+    assert!(!is_correct_answer("never gonna let you down", &answers));
+    assert!(!is_correct_answer("rick", &answers));
+    assert!(!is_correct_answer("", &answers));
+    assert!(!is_correct_answer("!!!", &answers));
+}
+
+#[test]
+fn test_get_similarity() {
+    assert_eq!(get_similarity("", ""), 1.0);
+    assert_eq!(get_similarity("queen", "queen"), 1.0);
+    assert_eq!(get_similarity("abc", ""), 0.0);
+    assert_eq!(get_similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+}
+
+#[test]
+fn test_get_clip_start() {
+    assert_eq!(get_clip_start(None), Duration::ZERO);
+    assert_eq!(
+        get_clip_start(Some(Duration::from_secs(15))),
+        Duration::ZERO
+    );
+    assert_eq!(
+        get_clip_start(Some(Duration::from_secs(20))),
+        Duration::ZERO
+    );
+    assert_eq!(
+        get_clip_start(Some(Duration::from_secs(60))),
+        Duration::from_secs(20)
+    );
+    assert_eq!(
+        get_clip_start(Some(Duration::from_secs(600))),
+        Duration::from_secs(60)
+    );
+}
+
+#[test]
+fn test_pick_choices() {
+    let pool: Vec<String> = ["A", "B", "C", "D", "E", "F"]
+        .iter()
+        .map(|title| title.to_string())
+        .collect();
+
+    for _ in 0..20 {
+        let (choices, correct) = pick_choices("A", &pool, 4);
+
+        assert_eq!(choices.len(), 4);
+        assert_eq!(choices[correct], "A");
+        assert_eq!(choices.iter().filter(|choice| *choice == "A").count(), 1);
+
+        let mut unique = choices.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+    }
+
+    // repeated titles in a playlist don't make for more choices
+    let pool: Vec<String> = ["A", "B", "B"]
+        .iter()
+        .map(|title| title.to_string())
+        .collect();
+    let (choices, correct) = pick_choices("A", &pool, 4);
+    assert_eq!(choices.len(), 2);
+    assert_eq!(choices[correct], "A");
+}
+
+#[test]
+fn test_rank_scores() {
+    let scores = HashMap::from([
+        (UserId::new(3), 2),
+        (UserId::new(2), 5),
+        (UserId::new(1), 2),
+    ]);
+
+    assert_eq!(
+        rank_scores(&scores),
+        vec![
+            (UserId::new(2), 5),
+            (UserId::new(1), 2),
+            (UserId::new(3), 2)
+        ]
+    );
+    assert!(rank_scores(&HashMap::new()).is_empty());
+}
+
+#[test]
+fn test_is_blocked_during_quiz() {
+    assert!(is_blocked_during_quiz("np"));
+    assert!(is_blocked_during_quiz("queue"));
+    assert!(is_blocked_during_quiz("skip"));
+    assert!(is_blocked_during_quiz("play"));
+    assert!(is_blocked_during_quiz("pause"));
+    assert!(is_blocked_during_quiz("resume"));
+    assert!(is_blocked_during_quiz("autopause"));
+
+    assert!(!is_blocked_during_quiz("quiz"));
+    assert!(!is_blocked_during_quiz("leave"));
+    assert!(!is_blocked_during_quiz("settings"));
+}
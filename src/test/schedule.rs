@@ -0,0 +1,81 @@
+use serenity::model::id::{ChannelId, GuildId, UserId};
+
+use crate::{
+    commands::schedule::{next_occurrence, parse_time_of_day},
+    guild::schedule::{GuildSchedules, SECONDS_PER_DAY, SECONDS_PER_WEEK, Schedule},
+};
+
+#[test]
+fn test_parse_time_of_day() {
+    assert_eq!(parse_time_of_day("21:30"), Some(77400));
+    assert_eq!(parse_time_of_day("7:05"), Some(25500));
+    assert_eq!(parse_time_of_day("00:00"), Some(0));
+
+    assert_eq!(parse_time_of_day("24:00"), None);
+    assert_eq!(parse_time_of_day("12:60"), None);
+    assert_eq!(parse_time_of_day("12:5"), None);
+    assert_eq!(parse_time_of_day("1230"), None);
+    assert_eq!(parse_time_of_day("noon"), None);
+}
+
+#[test]
+fn test_next_occurrence() {
+    // the epoch was a thursday at midnight
+    assert_eq!(next_occurrence(0, 77400, None), 77400);
+    assert_eq!(next_occurrence(80000, 77400, None), SECONDS_PER_DAY + 77400);
+
+    assert_eq!(next_occurrence(0, 77400, Some(3)), 77400);
+    assert_eq!(
+        next_occurrence(80000, 77400, Some(3)),
+        SECONDS_PER_WEEK + 77400
+    );
+    assert_eq!(
+        next_occurrence(0, 3600, Some(0)),
+        4 * SECONDS_PER_DAY + 3600
+    );
+    assert_eq!(
+        next_occurrence(0, 3600, Some(6)),
+        3 * SECONDS_PER_DAY + 3600
+    );
+}
+
+#[test]
+fn test_schedule_advance() {
+    let mut schedule = Schedule {
+        id: 1,
+        channel_id: ChannelId::new(1),
+        query: "lofi radio".to_string(),
+        next_run: 100,
+        weekly: false,
+        created_by: UserId::new(1),
+    };
+
+    assert!(schedule.advance(50));
+    assert!(!schedule.advance(100));
+
+    // weekly schedules skip whatever they missed
+    schedule.weekly = true;
+    assert!(schedule.advance(100));
+    assert_eq!(schedule.next_run, 100 + SECONDS_PER_WEEK);
+
+    assert!(schedule.advance(100 + 3 * SECONDS_PER_WEEK + 5));
+    assert_eq!(schedule.next_run, 100 + 4 * SECONDS_PER_WEEK);
+}
+
+#[test]
+fn test_guild_schedules_ids() {
+    let mut guild_schedules = GuildSchedules::new(GuildId::new(1));
+    let (channel_id, user_id) = (ChannelId::new(1), UserId::new(1));
+
+    let first = guild_schedules.add(channel_id, "a".to_string(), 0, false, user_id);
+    let second = guild_schedules.add(channel_id, "b".to_string(), 0, true, user_id);
+    assert_eq!((first.id, second.id), (1, 2));
+
+    // ids aren't handed out again once their schedule is gone
+    assert_eq!(guild_schedules.remove(2), Some(second));
+    assert_eq!(guild_schedules.remove(2), None);
+
+    let third = guild_schedules.add(channel_id, "c".to_string(), 0, false, user_id);
+    assert_eq!(third.id, 3);
+    assert_eq!(guild_schedules.get(1), Some(&first));
+}
@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+use crate::commands::sleep::parse_duration;
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("45"), Some(Duration::from_secs(45 * 60)));
+    assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+    assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+    assert_eq!(parse_duration(" 1h 15m "), Some(Duration::from_secs(4500)));
+    assert_eq!(parse_duration("2H"), Some(Duration::from_secs(7200)));
+
+    assert_eq!(parse_duration(""), None);
+    assert_eq!(parse_duration("0"), None);
+    assert_eq!(parse_duration("0m"), None);
+    assert_eq!(parse_duration("h"), None);
+    assert_eq!(parse_duration("1h30"), None);
+    assert_eq!(parse_duration("10 minutes"), None);
+    assert_eq!(parse_duration("99999999999999999999h"), None);
+}
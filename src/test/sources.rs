@@ -0,0 +1,238 @@
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    commands::play::QueryType,
+    sources::{
+        Source, SourceRegistry,
+        apple_music::{AppleMusic, AppleMusicLink},
+        deezer::{Deezer, DeezerMediaType},
+        direct::DirectLink,
+        page::{OEmbed, extract_meta_contents},
+        soundcloud::SoundCloud,
+        spotify::{MediaType, Spotify},
+        tidal::{Tidal, TidalLink},
+        youtube::YouTube,
+    },
+};
+
+fn url(link: &str) -> Url {
+    Url::parse(link).unwrap()
+}
+
+#[test]
+fn test_extract_meta_contents() {
+    let html = include_str!("fixtures/apple_music_playlist.html");
+
+    let titles = extract_meta_contents(html, "og:title");
+    assert_eq!(titles, vec!["Today's Hits on Apple Music"]);
+
+    let songs = extract_meta_contents(html, "music:song");
+    assert_eq!(songs.len(), 2);
+    assert!(extract_meta_contents(html, "og:image").is_empty());
+}
+
+#[test]
+fn test_apple_music_links() {
+    let link = url("https://music.apple.com/us/album/discovery/697194953?i=697195462");
+    assert_eq!(
+        AppleMusic::parse_link(&link),
+        Some(AppleMusicLink::Song("697195462".to_string()))
+    );
+
+    let link = url("https://music.apple.com/gb/album/discovery/697194953");
+    assert_eq!(
+        AppleMusic::parse_link(&link),
+        Some(AppleMusicLink::Album("697194953".to_string()))
+    );
+
+    let link =
+        url("https://music.apple.com/us/playlist/todays-hits/pl.f4d106fed2bd41149aaacabb233eb5eb");
+    assert_eq!(
+        AppleMusic::parse_link(&link),
+        Some(AppleMusicLink::Playlist)
+    );
+
+    assert!(AppleMusic.matches(&link));
+    assert!(!AppleMusic.matches(&url("https://www.apple.com/music/")));
+}
+
+#[test]
+fn test_apple_music_parsing() {
+    let value: Value =
+        serde_json::from_str(include_str!("fixtures/itunes_lookup_album.json")).unwrap();
+    assert_eq!(
+        AppleMusic::parse_lookup(&value),
+        vec!["Daft Punk - One More Time", "Daft Punk - Aerodynamic"]
+    );
+
+    let html = include_str!("fixtures/apple_music_playlist.html");
+    assert_eq!(
+        AppleMusic::parse_playlist_page(html),
+        vec!["1739659142", "1739659144"]
+    );
+}
+
+#[test]
+fn test_deezer_links() {
+    let link = url("https://www.deezer.com/en/album/302127");
+    assert_eq!(
+        Deezer::parse_link(&link),
+        Some((DeezerMediaType::Album, "302127".to_string()))
+    );
+
+    let link = url("https://www.deezer.com/track/3135556?utm_source=deezer");
+    assert_eq!(
+        Deezer::parse_link(&link),
+        Some((DeezerMediaType::Track, "3135556".to_string()))
+    );
+
+    assert_eq!(
+        Deezer::parse_link(&url("https://www.deezer.com/en/artist/27")),
+        None
+    );
+    assert!(Deezer.matches(&url("https://deezer.page.link/abcdef")));
+}
+
+#[test]
+fn test_deezer_parsing() {
+    let value: Value = serde_json::from_str(include_str!("fixtures/deezer_album.json")).unwrap();
+    assert_eq!(
+        Deezer::parse_tracks(DeezerMediaType::Album, &value),
+        vec!["Daft Punk - One More Time", "Daft Punk - Aerodynamic"]
+    );
+
+    let value: Value = serde_json::from_str(include_str!("fixtures/deezer_track.json")).unwrap();
+    assert_eq!(
+        Deezer::parse_tracks(DeezerMediaType::Track, &value),
+        vec!["Daft Punk - Digital Love"]
+    );
+}
+
+#[test]
+fn test_tidal() {
+    let link = url("https://tidal.com/browse/track/17214561");
+    assert_eq!(
+        Tidal::parse_link(&link),
+        Some(TidalLink::Track("17214561".to_string()))
+    );
+
+    let link = url("https://listen.tidal.com/album/17214560");
+    assert_eq!(
+        Tidal::parse_link(&link),
+        Some(TidalLink::Album("17214560".to_string()))
+    );
+
+    let oembed: OEmbed = serde_json::from_str(include_str!("fixtures/tidal_oembed.json")).unwrap();
+    assert_eq!(
+        Tidal::oembed_to_keywords(&oembed),
+        Some("Daft Punk - Get Lucky".to_string())
+    );
+
+    let html = include_str!("fixtures/tidal_album.html");
+    assert_eq!(
+        Tidal::parse_collection_page(html),
+        vec![
+            "https://tidal.com/browse/track/17214561",
+            "https://tidal.com/browse/track/17214562"
+        ]
+    );
+}
+
+#[test]
+fn test_soundcloud() {
+    let query = SoundCloud::classify(&url("https://soundcloud.com/daftpunk/get-lucky"));
+    assert!(matches!(query, Some(QueryType::VideoLink(_))));
+
+    let query = SoundCloud::classify(&url("https://soundcloud.com/daftpunk/sets/discovery"));
+    assert!(matches!(query, Some(QueryType::PlaylistLink(_))));
+
+    let query = SoundCloud::classify(&url("https://soundcloud.com/daftpunk"));
+    assert!(matches!(query, Some(QueryType::PlaylistLink(_))));
+
+    assert!(SoundCloud::classify(&url("https://soundcloud.com/")).is_none());
+}
+
+#[test]
+fn test_merge_query_types() {
+    let merged = QueryType::merge(vec![
+        QueryType::Keywords("a".to_string()),
+        QueryType::Keywords("b".to_string()),
+    ]);
+    assert!(matches!(merged, Some(QueryType::KeywordList(list)) if list == vec!["a", "b"]));
+
+    let merged = QueryType::merge(vec![QueryType::VideoLink("https://a".to_string())]);
+    assert!(matches!(merged, Some(QueryType::VideoLink(_))));
+
+    assert!(QueryType::merge(Vec::new()).is_none());
+}
+
+#[test]
+fn test_source_registry() {
+    let registry = SourceRegistry::new();
+    let name_of = |link: &str| {
+        registry
+            .find(&url(link))
+            .map(|source| source.metadata().name)
+    };
+
+    assert_eq!(
+        name_of("https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC"),
+        Some("Spotify")
+    );
+    assert_eq!(
+        name_of("https://www.deezer.com/track/3135556"),
+        Some("Deezer")
+    );
+    assert_eq!(name_of("https://example.com/audio/song.mp3"), Some("HTTP"));
+    assert_eq!(
+        name_of("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+        Some("yt-dlp")
+    );
+    assert_eq!(name_of("https://vimeo.com/76979871"), Some("yt-dlp"));
+    assert_eq!(name_of("file:///home/parrot/song.mp3"), None);
+}
+
+#[test]
+fn test_spotify_links() {
+    let (media_type, id) =
+        Spotify::parse_link("https://open.spotify.com/album/2noRn2Aes5aoNVsU6iWThc?si=abc")
+            .unwrap();
+    assert_eq!(media_type, MediaType::Album);
+    assert_eq!(id, "2noRn2Aes5aoNVsU6iWThc");
+
+    assert!(Spotify::parse_link("https://open.spotify.com/artist/4tZwfgrHOc3mvqYlEYSvVi").is_err());
+    assert_eq!(
+        Spotify::build_query("Daft Punk", "Voyager"),
+        "Daft Punk - Voyager"
+    );
+}
+
+#[tokio::test]
+async fn test_youtube_resolve() {
+    let http_client = reqwest::Client::new();
+
+    let link = url("https://www.youtube.com/watch?v=dQw4w9WgXcQ");
+    let queries = YouTube.resolve(&http_client, &link).await.unwrap();
+    assert!(matches!(queries.as_slice(), [QueryType::VideoLink(_)]));
+
+    let link = url("https://www.youtube.com/playlist?list=PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI");
+    let queries = YouTube.resolve(&http_client, &link).await.unwrap();
+    assert!(matches!(queries.as_slice(), [QueryType::PlaylistLink(_)]));
+}
+
+#[tokio::test]
+async fn test_direct_link_resolve() {
+    let http_client = reqwest::Client::new();
+
+    assert!(DirectLink.matches(&url("https://example.com/Song.FLAC")));
+    assert!(!DirectLink.matches(&url("https://example.com/watch?v=song.mp3")));
+
+    let link = url("https://example.com/audio/song.mp3");
+    let queries = DirectLink.resolve(&http_client, &link).await.unwrap();
+    assert!(matches!(queries.as_slice(), [QueryType::DirectLink(_)]));
+
+    let metadata = DirectLink::build_metadata(link.as_str());
+    assert_eq!(metadata.title.as_deref(), Some("song.mp3"));
+    assert_eq!(metadata.source_url.as_deref(), Some(link.as_str()));
+}
@@ -0,0 +1,68 @@
+use crate::{
+    commands::undo::{RestoredTrack, plan_restore},
+    guild::cache::UndoTrack,
+};
+
+fn undo_track(uuid: &str, removed: bool) -> UndoTrack {
+    UndoTrack {
+        uuid: uuid.to_string(),
+        metadata: None,
+        requester: None,
+        removed,
+    }
+}
+
+#[test]
+fn test_plan_restore_cleared() {
+    let snapshot = [
+        undo_track("a", true),
+        undo_track("b", true),
+        undo_track("c", true),
+    ];
+
+    assert_eq!(
+        plan_restore(&snapshot, &[]),
+        vec![
+            RestoredTrack::Missing(0),
+            RestoredTrack::Missing(1),
+            RestoredTrack::Missing(2),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_restore_shuffled() {
+    let snapshot = [
+        undo_track("a", false),
+        undo_track("b", false),
+        undo_track("c", false),
+    ];
+
+    assert_eq!(
+        plan_restore(&snapshot, &["c", "a", "b"]),
+        vec![
+            RestoredTrack::Queued(1),
+            RestoredTrack::Queued(2),
+            RestoredTrack::Queued(0),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_restore_changed_since() {
+    // a has played since, b was removed and d was queued afterwards
+    let snapshot = [
+        undo_track("a", false),
+        undo_track("b", true),
+        undo_track("c", false),
+    ];
+
+    assert_eq!(
+        plan_restore(&snapshot, &["c", "d"]),
+        vec![
+            RestoredTrack::Missing(1),
+            RestoredTrack::Queued(0),
+            RestoredTrack::Queued(1),
+        ]
+    );
+}
@@ -1,6 +1,8 @@
-use std::time::Duration;
+use std::time::{Duration, UNIX_EPOCH};
 
-use crate::utils::get_human_readable_timestamp;
+use crate::utils::{
+    create_progress_bar, get_discord_timestamp, get_human_readable_timestamp, truncate_text,
+};
 
 #[test]
 fn test_get_human_readable_timestamp() {
@@ -22,3 +24,40 @@ fn test_get_human_readable_timestamp() {
     let result = get_human_readable_timestamp(None);
     assert_eq!(result, "∞");
 }
+
+#[test]
+fn test_get_discord_timestamp() {
+    let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    assert_eq!(get_discord_timestamp(time), "<t:1700000000:R>");
+
+    assert_eq!(get_discord_timestamp(UNIX_EPOCH), "<t:0:R>");
+}
+
+#[test]
+fn test_create_progress_bar() {
+    let duration = Some(Duration::from_secs(100));
+
+    let result = create_progress_bar(Duration::ZERO, duration);
+    assert!(result.starts_with("🔘"));
+    assert_eq!(result.matches('▬').count(), 17);
+
+    let result = create_progress_bar(Duration::from_secs(100), duration);
+    assert!(result.ends_with("🔘"));
+
+    let result = create_progress_bar(Duration::from_secs(50), duration);
+    assert_eq!(result, format!("{}🔘{}", "▬".repeat(9), "▬".repeat(8)));
+
+    // past the end or without a duration, the knob stays on the bar
+    let result = create_progress_bar(Duration::from_secs(500), duration);
+    assert!(result.ends_with("🔘"));
+
+    let result = create_progress_bar(Duration::from_secs(500), None);
+    assert!(result.starts_with("🔘"));
+}
+
+#[test]
+fn test_truncate_text() {
+    assert_eq!(truncate_text("Parrot", 6), "Parrot");
+    assert_eq!(truncate_text("Parrot", 4), "Par…");
+    assert_eq!(truncate_text("Big parrot", 5), "Big…");
+}
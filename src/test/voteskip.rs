@@ -0,0 +1,10 @@
+use crate::commands::voteskip::get_required_votes;
+
+#[test]
+fn test_get_required_votes() {
+    assert_eq!(get_required_votes(4, 50), 2);
+    assert_eq!(get_required_votes(5, 50), 3);
+    assert_eq!(get_required_votes(3, 100), 3);
+    assert_eq!(get_required_votes(10, 1), 1);
+    assert_eq!(get_required_votes(0, 50), 1);
+}
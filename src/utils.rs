@@ -10,7 +10,10 @@ use serenity::{
     prelude::{TypeMap, TypeMapKey},
 };
 use songbird::{input::AuxMetadata, tracks::TrackHandle};
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::RwLock;
 use url::Url;
 
@@ -18,6 +21,8 @@ use crate::{
     errors::ParrotError, guild::metadata_store::MetadataStore, messaging::message::ParrotMessage,
 };
 
+const PROGRESS_BAR_LENGTH: usize = 18;
+
 pub struct AuxMetadataTypeMapKey;
 
 impl TypeMapKey for AuxMetadataTypeMapKey {
@@ -42,6 +47,25 @@ pub async fn create_response_text(
     create_embed_response(http, interaction, embed).await
 }
 
+pub async fn create_ephemeral_response_text(
+    http: &Arc<Http>,
+    interaction: &mut CommandInteraction,
+    content: &str,
+) -> Result<(), ParrotError> {
+    let embed = CreateEmbed::default().description(content);
+    interaction
+        .create_response(
+            &http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .add_embed(embed)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .map_err(Into::into)
+}
+
 pub async fn edit_response(
     http: &Arc<Http>,
     interaction: &mut CommandInteraction,
@@ -143,7 +167,15 @@ pub async fn create_now_playing_embed(
         )
         .url(metadata.source_url.clone().unwrap());
 
-    let position = get_human_readable_timestamp(Some(track.get_info().await.unwrap().position));
+    // the track might have ended since it was looked up
+    let position = track
+        .get_info()
+        .await
+        .map_err(|_| ParrotError::NothingPlaying)?
+        .position;
+    embed = embed.description(create_progress_bar(position, metadata.duration));
+
+    let position = get_human_readable_timestamp(Some(position));
     let duration = get_human_readable_timestamp(metadata.duration);
 
     embed = embed.field("Progress", format!(">>> {} / {}", position, duration), true);
@@ -153,7 +185,9 @@ pub async fn create_now_playing_embed(
         None => embed.field("Channel", ">>> N/A", true),
     };
 
-    embed = embed.thumbnail(metadata.thumbnail.unwrap());
+    if let Some(thumbnail) = metadata.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
 
     let source_url = metadata.source_url.unwrap();
 
@@ -162,6 +196,34 @@ pub async fn create_now_playing_embed(
     Ok(embed.footer(CreateEmbedFooter::new(footer_text).icon_url(footer_icon_url)))
 }
 
+/// Cuts text down to at most `limit` characters, marking where it was cut.
+pub fn truncate_text(text: &str, limit: usize) -> String {
+    match text.chars().count() > limit {
+        true => {
+            let text: String = text.chars().take(limit - 1).collect();
+            format!("{}…", text.trim_end())
+        }
+        false => text.to_string(),
+    }
+}
+
+/// Draws how far into a track playback is, e.g. `▬▬▬🔘▬▬▬▬▬▬`.
+/// Tracks without a known duration, like livestreams, keep the knob at the start.
+pub fn create_progress_bar(position: Duration, duration: Option<Duration>) -> String {
+    let progress = match duration {
+        Some(duration) if !duration.is_zero() => {
+            (position.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0)
+        }
+        _ => 0.0,
+    };
+
+    let knob = ((PROGRESS_BAR_LENGTH - 1) as f64 * progress).round() as usize;
+
+    (0..PROGRESS_BAR_LENGTH)
+        .map(|idx| if idx == knob { "🔘" } else { "▬" })
+        .collect()
+}
+
 pub fn get_footer_info(url: &str) -> (String, String) {
     let url_data = Url::parse(url).unwrap();
     let domain = url_data.host_str().unwrap();
@@ -193,6 +255,16 @@ pub fn get_human_readable_timestamp(duration: Option<Duration>) -> String {
     }
 }
 
+/// Formats a point in time as a Discord timestamp, which clients render relative to now.
+pub fn get_discord_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+
+    format!("<t:{}:R>", seconds)
+}
+
 pub fn compare_domains(domain: &str, subdomain: &str) -> bool {
     subdomain == domain || subdomain.ends_with(domain)
 }